@@ -0,0 +1,278 @@
+use bevy :: prelude :: *;
+use bevy :: utils :: HashMap;
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum CameraAction {
+	// Reader mode
+	ScrollLineUp,
+	ScrollLineDown,
+	PageUp,
+	PageDown,
+	HalfPageUp,
+	HalfPageDown,
+	DocumentStart,
+	DocumentEnd,
+
+	// Reader, Follow and Orbit modes
+	ZoomIn,
+	ZoomOut,
+	ZoomReset,
+
+	// every mode
+	TogglePerspective,
+	SwitchMode,
+
+	// Fly mode
+	MoveForward,
+	MoveBackward,
+	MoveLeft,
+	MoveRight,
+	MoveUp,
+	MoveDown,
+	Sprint,
+}
+
+impl CameraAction {
+	pub const ALL: [CameraAction; 20] = [
+		CameraAction::ScrollLineUp,
+		CameraAction::ScrollLineDown,
+		CameraAction::PageUp,
+		CameraAction::PageDown,
+		CameraAction::HalfPageUp,
+		CameraAction::HalfPageDown,
+		CameraAction::DocumentStart,
+		CameraAction::DocumentEnd,
+		CameraAction::ZoomIn,
+		CameraAction::ZoomOut,
+		CameraAction::ZoomReset,
+		CameraAction::TogglePerspective,
+		CameraAction::SwitchMode,
+		CameraAction::MoveForward,
+		CameraAction::MoveBackward,
+		CameraAction::MoveLeft,
+		CameraAction::MoveRight,
+		CameraAction::MoveUp,
+		CameraAction::MoveDown,
+		CameraAction::Sprint,
+	];
+
+	// movement keys are held together with each other (Shift is MoveDown in default profile) so extra modifiers shouldn't block them
+	fn ignores_extra_modifiers(&self) -> bool {
+		matches!(self,
+			CameraAction::MoveForward |
+			CameraAction::MoveBackward |
+			CameraAction::MoveLeft |
+			CameraAction::MoveRight |
+			CameraAction::MoveUp |
+			CameraAction::MoveDown |
+			CameraAction::Sprint
+		)
+	}
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum Modifier {
+	Ctrl,
+	Shift,
+	Alt,
+	Super,
+}
+
+impl Modifier {
+	const ALL: [Modifier; 4] = [Modifier::Ctrl, Modifier::Shift, Modifier::Alt, Modifier::Super];
+
+	/// Left and right variants of the modifier
+	pub fn keys(&self) -> [KeyCode; 2] {
+		match self {
+			Modifier::Ctrl	=> [KeyCode::LControl, KeyCode::RControl],
+			Modifier::Shift	=> [KeyCode::LShift, KeyCode::RShift],
+			Modifier::Alt	=> [KeyCode::LAlt, KeyCode::RAlt],
+			Modifier::Super	=> [KeyCode::LWin, KeyCode::RWin],
+		}
+	}
+
+	pub fn from_key(key: KeyCode) -> Option<Modifier> {
+		Modifier::ALL.into_iter().find(|modifier| modifier.keys().contains(&key))
+	}
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum InputTrigger {
+	Key(KeyCode),
+	Mouse(MouseButton),
+}
+
+/// Trigger with modifiers that have to be held. Modifiers that are not listed must not be held (except for movement actions)
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Binding {
+	pub trigger		: InputTrigger,
+	pub modifiers	: Vec<Modifier>,
+	/// ignores the press itself and only reacts while trigger is held after that, so the press can go to the app (e.g. to move caret)
+	pub held_only	: bool,
+}
+
+impl Binding {
+	pub fn key(key: KeyCode) -> Self {
+		Self { trigger: InputTrigger::Key(key), modifiers: Vec::new(), held_only: false }
+	}
+
+	pub fn mouse(button: MouseButton) -> Self {
+		Self { trigger: InputTrigger::Mouse(button), modifiers: Vec::new(), held_only: false }
+	}
+
+	pub fn with(mut self, modifier: Modifier) -> Self {
+		self.modifiers.push(modifier);
+		self
+	}
+
+	pub fn held_only(mut self) -> Self {
+		self.held_only = true;
+		self
+	}
+
+	fn triggered(&self, keys: &Input<KeyCode>, mouse: &Input<MouseButton>, just: bool, extra_modifiers_allowed: bool) -> bool {
+		let (just_pressed, pressed, trigger_modifier) = match self.trigger {
+			InputTrigger::Key(key) => (keys.just_pressed(key), keys.pressed(key), Modifier::from_key(key)),
+			InputTrigger::Mouse(button) => (mouse.just_pressed(button), mouse.pressed(button), None),
+		};
+
+		let triggered = match (just, self.held_only) {
+			(true, false)	=> just_pressed,
+			(false, false)	=> pressed,
+			(true, true)	=> false,
+			(false, true)	=> pressed && !just_pressed,
+		};
+
+		if !triggered {
+			return false
+		}
+
+		Modifier::ALL.iter()
+			// trigger itself can be a modifier key
+			.filter(|modifier| Some(**modifier) != trigger_modifier)
+			.all(|modifier| {
+				let held		= keys.any_pressed(modifier.keys());
+				let required	= self.modifiers.contains(modifier);
+
+				held == required || (held && extra_modifiers_allowed)
+			})
+	}
+}
+
+/// Rebindable input for every camera mode, several bindings per action are allowed
+#[derive(Clone, Debug)]
+pub struct ActionMap {
+	bindings: HashMap<CameraAction, Vec<Binding>>,
+}
+
+impl Default for ActionMap {
+	fn default() -> Self {
+		Self::profile_default()
+	}
+}
+
+impl ActionMap {
+	pub fn empty() -> Self {
+		Self { bindings: HashMap::default() }
+	}
+
+	pub fn profile_default() -> Self {
+		let mut map = Self::empty();
+
+		map
+			.bind(CameraAction::ScrollLineUp,		Binding::key(KeyCode::Up))
+			.bind(CameraAction::ScrollLineDown,		Binding::key(KeyCode::Down))
+			.bind(CameraAction::PageUp,				Binding::key(KeyCode::PageUp))
+			.bind(CameraAction::PageDown,			Binding::key(KeyCode::PageDown))
+			.bind(CameraAction::HalfPageUp,			Binding::key(KeyCode::PageUp).with(Modifier::Shift))
+			.bind(CameraAction::HalfPageDown,		Binding::key(KeyCode::PageDown).with(Modifier::Shift))
+			.bind(CameraAction::DocumentStart,		Binding::key(KeyCode::Home).with(Modifier::Ctrl))
+			.bind(CameraAction::DocumentEnd,		Binding::key(KeyCode::End).with(Modifier::Ctrl))
+
+			.bind(CameraAction::ZoomIn,				Binding::key(KeyCode::Equals).with(Modifier::Ctrl))
+			.bind(CameraAction::ZoomOut,			Binding::key(KeyCode::Minus).with(Modifier::Ctrl))
+			.bind(CameraAction::ZoomReset,			Binding::key(KeyCode::Key0).with(Modifier::Ctrl))
+
+			.bind(CameraAction::TogglePerspective,	Binding::key(KeyCode::Return).with(Modifier::Ctrl))
+			// no default for SwitchMode, apps usually drive modes themselves
+
+			.bind(CameraAction::MoveForward,		Binding::key(KeyCode::W))
+			.bind(CameraAction::MoveBackward,		Binding::key(KeyCode::S))
+			.bind(CameraAction::MoveLeft,			Binding::key(KeyCode::A))
+			.bind(CameraAction::MoveRight,			Binding::key(KeyCode::D))
+			.bind(CameraAction::MoveUp,				Binding::key(KeyCode::Space))
+			.bind(CameraAction::MoveDown,			Binding::key(KeyCode::LShift))
+			.bind(CameraAction::Sprint,				Binding::key(KeyCode::LControl))
+		;
+
+		map
+	}
+
+	/// Default profile plus vim navigation. Key sequences aren't supported so gg is just g
+	pub fn profile_vim() -> Self {
+		let mut map = Self::profile_default();
+
+		map
+			.bind(CameraAction::ScrollLineUp,		Binding::key(KeyCode::K))
+			.bind(CameraAction::ScrollLineDown,		Binding::key(KeyCode::J))
+			.bind(CameraAction::PageUp,				Binding::key(KeyCode::B).with(Modifier::Ctrl))
+			.bind(CameraAction::PageDown,			Binding::key(KeyCode::F).with(Modifier::Ctrl))
+			.bind(CameraAction::HalfPageUp,			Binding::key(KeyCode::U).with(Modifier::Ctrl))
+			.bind(CameraAction::HalfPageDown,		Binding::key(KeyCode::D).with(Modifier::Ctrl))
+			.bind(CameraAction::DocumentStart,		Binding::key(KeyCode::G))
+			.bind(CameraAction::DocumentEnd,		Binding::key(KeyCode::G).with(Modifier::Shift))
+		;
+
+		map
+	}
+
+	/// Default profile plus emacs navigation
+	pub fn profile_emacs() -> Self {
+		let mut map = Self::profile_default();
+
+		map
+			.bind(CameraAction::ScrollLineUp,		Binding::key(KeyCode::P).with(Modifier::Ctrl))
+			.bind(CameraAction::ScrollLineDown,		Binding::key(KeyCode::N).with(Modifier::Ctrl))
+			.bind(CameraAction::PageUp,				Binding::key(KeyCode::V).with(Modifier::Alt))
+			.bind(CameraAction::PageDown,			Binding::key(KeyCode::V).with(Modifier::Ctrl))
+			.bind(CameraAction::DocumentStart,		Binding::key(KeyCode::Comma).with(Modifier::Alt).with(Modifier::Shift))
+			.bind(CameraAction::DocumentEnd,		Binding::key(KeyCode::Period).with(Modifier::Alt).with(Modifier::Shift))
+		;
+
+		map
+	}
+
+	pub fn bind(&mut self, action: CameraAction, binding: Binding) -> &mut Self {
+		self.bindings.entry(action).or_default().push(binding);
+		self
+	}
+
+	pub fn unbind(&mut self, action: CameraAction) -> &mut Self {
+		self.bindings.remove(&action);
+		self
+	}
+
+	pub fn bindings(&self, action: CameraAction) -> &[Binding] {
+		self.bindings.get(&action).map_or(&[], |bindings| bindings.as_slice())
+	}
+
+	pub fn pressed(&self, action: CameraAction, keys: &Input<KeyCode>, mouse: &Input<MouseButton>) -> bool {
+		self.bindings(action).iter().any(|binding| binding.triggered(keys, mouse, false, action.ignores_extra_modifiers()))
+	}
+
+	pub fn just_pressed(&self, action: CameraAction, keys: &Input<KeyCode>, mouse: &Input<MouseButton>) -> bool {
+		self.bindings(action).iter().any(|binding| binding.triggered(keys, mouse, true, action.ignores_extra_modifiers()))
+	}
+
+	/// 1.0 if only plus is pressed, -1.0 if only minus is pressed, 0.0 otherwise
+	pub fn axis(&self, plus: CameraAction, minus: CameraAction, keys: &Input<KeyCode>, mouse: &Input<MouseButton>) -> f32 {
+		let mut axis = 0.0;
+		if self.pressed(plus, keys, mouse) {
+			axis += 1.0;
+		}
+		if self.pressed(minus, keys, mouse) {
+			axis -= 1.0;
+		}
+		axis
+	}
+}
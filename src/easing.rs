@@ -0,0 +1,175 @@
+use bevy :: prelude :: *;
+
+use lerp :: Lerp;
+
+// All camera easing is exponential decay towards the target: after easing_seconds about 63% of the distance is covered,
+// after three times that about 95%. Easing twice by dt ends up exactly where easing once by 2 * dt does,
+// so the motion is the same on any frame rate, including long frames of a sleeping app.
+
+/// Fraction of the remaining distance to cover in delta_seconds, zero or negative easing_seconds means instant
+pub fn ease_factor(delta_seconds: f32, easing_seconds: f32) -> f32 {
+	if easing_seconds <= 0.0 {
+		return 1.0
+	}
+
+	1.0 - (-delta_seconds / easing_seconds).exp()
+}
+
+pub fn ease(current: f32, target: f32, delta_seconds: f32, easing_seconds: f32) -> f32 {
+	current.lerp(target, ease_factor(delta_seconds, easing_seconds))
+}
+
+/// Motion curve of a single camera channel (zoom, rotation, translation, lean)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Easing {
+	/// covers about 63% of the remaining distance every `seconds`, follows moving targets smoothly and never overshoots
+	Exponential { seconds: f32 },
+	/// reaches the target in `seconds` with constant speed
+	Linear { seconds: f32 },
+	/// reaches the target in `seconds`, starts fast and slows down towards the end
+	Cubic { seconds: f32 },
+	/// reaches the target in `seconds`, speeds up and slows down smoothly
+	EaseInOut { seconds: f32 },
+	/// spring-damper, damping of 1.0 is critical (fastest without overshooting), below that it bounces
+	Spring { stiffness: f32, damping: f32 },
+}
+
+impl Easing {
+	/// Easing in between two others, parameters are interpolated if both are of the same kind
+	pub fn blend(&self, other: &Easing, t: f32) -> Easing {
+		use Easing :: *;
+
+		match (*self, *other) {
+			(Exponential { seconds: a }, Exponential { seconds: b })	=> Exponential { seconds: a.lerp(b, t) },
+			(Linear { seconds: a }, Linear { seconds: b })				=> Linear { seconds: a.lerp(b, t) },
+			(Cubic { seconds: a }, Cubic { seconds: b })				=> Cubic { seconds: a.lerp(b, t) },
+			(EaseInOut { seconds: a }, EaseInOut { seconds: b })		=> EaseInOut { seconds: a.lerp(b, t) },
+			(Spring { stiffness: sa, damping: da }, Spring { stiffness: sb, damping: db }) => Spring { stiffness: sa.lerp(sb, t), damping: da.lerp(db, t) },
+			_ => if t < 0.5 { *self } else { *other },
+		}
+	}
+
+	/// Moves current value towards target by delta_seconds, state has to be kept per channel between frames
+	pub fn step<T: Easable>(&self, current: T, target: T, delta_seconds: f32, state: &mut EasingState) -> T {
+		let curve: fn(f32) -> f32 = match *self {
+			Easing::Exponential { seconds } => {
+				state.reset();
+				return current.lerp_to(target, ease_factor(delta_seconds, seconds))
+			},
+			Easing::Spring { stiffness, damping } => {
+				state.elapsed = 0.0;
+				return spring(current, target, stiffness, damping, delta_seconds, state)
+			},
+			Easing::Linear { .. }		=> |t| t,
+			Easing::Cubic { .. }		=> |t| 1.0 - (1.0 - t).powi(3),
+			Easing::EaseInOut { .. }	=> |t| t * t * (3.0 - 2.0 * t),
+		};
+
+		let seconds			= self.seconds();
+
+		state.velocity		= Vec3::ZERO;
+
+		if current == target || seconds <= 0.0 {
+			state.elapsed	= 0.0;
+			return target
+		}
+
+		// target moving during animation doesn't restart it so the motion still ends in time
+		let from			= curve((state.elapsed / seconds).min(1.0));
+		state.elapsed		+= delta_seconds;
+		let to				= curve((state.elapsed / seconds).min(1.0));
+
+		if to >= 1.0 {
+			state.elapsed	= 0.0;
+			return target
+		}
+
+		// progress is turned into a fraction of what is left so target can change on the way
+		current.lerp_to(target, (to - from) / (1.0 - from))
+	}
+
+	/// Duration for fixed duration curves, time constant for exponential and none for springs
+	pub fn seconds(&self) -> f32 {
+		match *self {
+			Easing::Exponential { seconds } |
+			Easing::Linear { seconds } |
+			Easing::Cubic { seconds } |
+			Easing::EaseInOut { seconds } => seconds,
+			Easing::Spring { .. } => 0.0,
+		}
+	}
+}
+
+// springs are integrated in small steps because semi-implicit euler is only stable and frame rate independent enough for short ones
+const SPRING_STEP_SECONDS: f32 = 1.0 / 240.0;
+
+fn spring<T: Easable>(current: T, target: T, stiffness: f32, damping: f32, delta_seconds: f32, state: &mut EasingState) -> T {
+	let steps			= (delta_seconds / SPRING_STEP_SECONDS).ceil().max(1.0);
+	let step_seconds	= delta_seconds / steps;
+	let damping			= 2.0 * damping * stiffness.sqrt();
+
+	let mut value		= current;
+	for _ in 0..steps as usize {
+		let offset		= value.offset_to(target);
+		state.velocity	+= (offset * stiffness - state.velocity * damping) * step_seconds;
+		value			= value.offset_by(state.velocity * step_seconds);
+	}
+
+	// settled, avoid endless tiny motion
+	if value.offset_to(target).length() < 0.0001 && state.velocity.length() < 0.001 {
+		state.velocity	= Vec3::ZERO;
+		return target
+	}
+
+	value
+}
+
+/// Per channel state of easing that has to be kept between frames
+#[derive(Default, Clone, Copy, Debug)]
+pub struct EasingState {
+	elapsed		: f32,
+	velocity	: Vec3,
+}
+
+impl EasingState {
+	pub fn reset(&mut self) {
+		*self = Self::default();
+	}
+}
+
+/// Value that can be eased, springs work with offsets in up to three dimensions
+pub trait Easable: Copy + PartialEq {
+	fn lerp_to(self, target: Self, t: f32) -> Self;
+	fn offset_to(self, target: Self) -> Vec3;
+	fn offset_by(self, offset: Vec3) -> Self;
+}
+
+impl Easable for f32 {
+	fn lerp_to(self, target: Self, t: f32) -> Self { self.lerp(target, t) }
+	fn offset_to(self, target: Self) -> Vec3 { Vec3::X * (target - self) }
+	fn offset_by(self, offset: Vec3) -> Self { self + offset.x }
+}
+
+impl Easable for Vec2 {
+	fn lerp_to(self, target: Self, t: f32) -> Self { self.lerp(target, t) }
+	fn offset_to(self, target: Self) -> Vec3 { (target - self).extend(0.0) }
+	fn offset_by(self, offset: Vec3) -> Self { self + offset.truncate() }
+}
+
+impl Easable for Vec3 {
+	fn lerp_to(self, target: Self, t: f32) -> Self { self.lerp(target, t) }
+	fn offset_to(self, target: Self) -> Vec3 { target - self }
+	fn offset_by(self, offset: Vec3) -> Self { self + offset }
+}
+
+// rotations use angular offsets in world space
+impl Easable for Quat {
+	fn lerp_to(self, target: Self, t: f32) -> Self { self.slerp(target, t) }
+	fn offset_to(self, target: Self) -> Vec3 {
+		let delta = target * self.inverse();
+		// shortest way around
+		let delta = if delta.w < 0.0 { -delta } else { delta };
+		delta.to_scaled_axis()
+	}
+	fn offset_by(self, offset: Vec3) -> Self { (Quat::from_scaled_axis(offset) * self).normalize() }
+}
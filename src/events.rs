@@ -0,0 +1,108 @@
+use bevy :: prelude :: *;
+
+use super :: CameraMode;
+
+// Row offset protocol between reader camera and app:
+// 1. camera scrolls and sends RowScrollRequest with the amount of rows it wants app to scroll by
+// 2. app scrolls its text and answers with RowOffsetAck carrying its new row offset
+// 3. camera consumes all requests sent so far and keeps easing towards the new offset
+// RowOffsetChanged and VisibleRowsChanged are sent whenever the viewport itself changes
+// When app inserts or removes rows it sends RowsEdited and shifts its own row offset the same way camera does,
+// no request or ack is involved and the content under the viewport stays where it was. Acks sent in the same frame
+// as RowsEdited are taken as answered before the edit
+
+/// Camera wants app to scroll by given amount of rows. Requests are relative to each other, sum them up until answering with [`RowOffsetAck`]
+#[derive(Clone, Copy, Debug)]
+pub struct RowScrollRequest {
+	pub camera	: Entity,
+	pub rows	: i32,
+}
+
+/// App has applied every [`RowScrollRequest`] received so far and now has given row offset
+#[derive(Clone, Copy, Debug)]
+pub struct RowOffsetAck {
+	pub camera		: Entity,
+	pub row_offset	: u32,
+}
+
+/// App has replaced `removed` rows starting at logical `row` with `inserted` new ones in text of `target` entity.
+/// Rows below the edit move by `inserted - removed`, an offset inside removed rows moves to `row`
+#[derive(Clone, Copy, Debug)]
+pub struct RowsEdited {
+	pub target		: Entity,
+	pub row			: u32,
+	pub removed		: u32,
+	pub inserted	: u32,
+}
+
+impl RowsEdited {
+	/// Where given logical row ends up after the edit
+	pub fn anchored(&self, row: u32) -> u32 {
+		if row < self.row {
+			row
+		} else if row - self.row >= self.removed {
+			row - self.removed + self.inserted
+		} else {
+			self.row
+		}
+	}
+}
+
+/// Row the camera is actually looking at (same as [`crate::ReaderCamera::row_offset_out`]) has changed
+#[derive(Clone, Copy, Debug)]
+pub struct RowOffsetChanged {
+	pub camera		: Entity,
+	pub row_offset	: u32,
+}
+
+/// Amount of rows/columns fitting into the viewport has changed
+#[derive(Clone, Copy, Debug)]
+pub struct VisibleRowsChanged {
+	pub camera			: Entity,
+	pub visible_rows	: f32,
+	pub visible_columns	: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CameraCommand {
+	/// Make given row the first visible one
+	ScrollTo(u32),
+	/// Scroll by given amount of rows, negative is towards row 0 which is up unless text is bottom anchored
+	ScrollBy(i32),
+	/// Scroll by one screen minus ReaderCamera::page_overlap_rows, up and down the screen for bottom anchored text too
+	PageUp,
+	PageDown,
+	/// Scroll by half of the screen
+	HalfPageUp,
+	HalfPageDown,
+	/// Row 0, the bottom of bottom anchored text
+	DocumentStart,
+	DocumentEnd,
+	/// Change zoom like mouse wheel does, positive is further from text
+	ZoomBy(f32),
+	/// Make given row the one in the middle of the viewport
+	CenterOnRow(u32),
+	/// Scroll as little as possible to make every row in start..=end visible, start wins if range doesn't fit
+	RevealRange { start: u32, end: u32 },
+	SetZoom(f32),
+	SetColumn(usize),
+	SetMode(CameraMode),
+	/// Switch between perspective and orthographic projection with a dolly zoom, see ReaderCamera::set_perspective
+	SetPerspective(bool),
+	/// Orbit mode: orbit around given entity and fit its bounds (text block, mesh Aabb or just position) into view
+	Focus(Entity),
+}
+
+/// Drives reader camera from code with the same easing as user input
+#[derive(Clone, Copy, Debug)]
+pub struct ReaderCameraCommand {
+	pub camera	: Entity,
+	pub command	: CameraCommand,
+}
+
+/// Camera has finished blending into given mode after [`crate::ReaderCamera::set_mode`]
+#[derive(Clone, Copy, Debug)]
+pub struct ModeTransitionFinished {
+	pub camera	: Entity,
+	pub mode	: CameraMode,
+}
@@ -0,0 +1,131 @@
+use bevy :: prelude :: *;
+
+/// Deadzone and inversion of a single gamepad axis
+#[derive(Clone, Copy, Debug)]
+pub struct GamepadAxisTuning {
+	/// values below deadzone are ignored, the rest is rescaled to 0..1 so there is no jump at the edge
+	pub deadzone	: f32,
+	pub invert		: bool,
+}
+
+impl Default for GamepadAxisTuning {
+	fn default() -> Self {
+		Self { deadzone: 0.15, invert: false }
+	}
+}
+
+impl GamepadAxisTuning {
+	pub fn apply(&self, value: f32) -> f32 {
+		let magnitude = value.abs();
+		if magnitude <= self.deadzone {
+			return 0.0
+		}
+
+		let value = value.signum() * ((magnitude - self.deadzone) / (1.0 - self.deadzone).max(f32::EPSILON)).min(1.0);
+
+		if self.invert { -value } else { value }
+	}
+}
+
+/// Gamepad controls for every camera mode:
+/// Reader - left stick scrolls (right stick x swipes), triggers zoom, d-pad up/down steps by line, left/right by page
+/// Fly - left stick moves, right stick looks around, triggers fly down/up
+/// Follow - right stick orbits, triggers zoom
+/// Orbit - right stick orbits, left stick pans, triggers zoom
+#[derive(Clone, Copy, Debug)]
+pub struct GamepadControl {
+	pub enabled					: bool,
+	/// None means any connected gamepad
+	pub gamepad					: Option<Gamepad>,
+	pub left_x					: GamepadAxisTuning,
+	pub left_y					: GamepadAxisTuning,
+	pub right_x					: GamepadAxisTuning,
+	pub right_y					: GamepadAxisTuning,
+	pub triggers				: GamepadAxisTuning,
+	/// reader scrolling speed at full stick deflection
+	pub scroll_rows_per_second	: f32,
+	/// reader swiping speed at full stick deflection
+	pub swipe_columns_per_second: f32,
+	/// fly and follow rotation speed at full stick deflection
+	pub look_degrees_per_second	: f32,
+	/// zoom change per second with fully pressed trigger
+	pub zoom_per_second			: f32,
+}
+
+impl Default for GamepadControl {
+	fn default() -> Self {
+		Self {
+			enabled					: true,
+			gamepad					: None,
+			left_x					: GamepadAxisTuning::default(),
+			left_y					: GamepadAxisTuning::default(),
+			right_x					: GamepadAxisTuning::default(),
+			right_y					: GamepadAxisTuning::default(),
+			triggers				: GamepadAxisTuning { deadzone: 0.05, invert: false },
+			scroll_rows_per_second	: 40.0,
+			swipe_columns_per_second: 40.0,
+			look_degrees_per_second	: 120.0,
+			zoom_per_second			: 20.0,
+		}
+	}
+}
+
+/// Gamepad state after deadzones and inversion, positive y is up, positive triggers is right trigger
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GamepadInput {
+	pub left			: Vec2,
+	pub right			: Vec2,
+	pub triggers		: f32,
+	pub dpad_up			: bool,
+	pub dpad_down		: bool,
+	pub dpad_left		: bool,
+	pub dpad_right		: bool,
+}
+
+impl GamepadInput {
+	pub fn is_active(&self) -> bool {
+		self.left != Vec2::ZERO || self.right != Vec2::ZERO || self.triggers != 0.0
+	}
+}
+
+pub fn read_gamepad(
+	control			: &GamepadControl,
+	gamepads		: &Gamepads,
+	axes			: &Axis<GamepadAxis>,
+	buttons			: &Input<GamepadButton>,
+	button_axes		: &Axis<GamepadButton>,
+) -> GamepadInput {
+	let mut input = GamepadInput::default();
+
+	if !control.enabled {
+		return input
+	}
+
+	for gamepad in gamepads.iter() {
+		if let Some(only) = control.gamepad {
+			if only != gamepad {
+				continue
+			}
+		}
+
+		let axis			= |axis_type| axes.get(GamepadAxis::new(gamepad, axis_type)).unwrap_or(0.0);
+		let button_axis		= |button_type| button_axes.get(GamepadButton::new(gamepad, button_type)).unwrap_or(0.0);
+		let just_pressed	= |button_type| buttons.just_pressed(GamepadButton::new(gamepad, button_type));
+
+		// several gamepads add up, clamped below
+		input.left			+= Vec2::new(control.left_x.apply(axis(GamepadAxisType::LeftStickX)), control.left_y.apply(axis(GamepadAxisType::LeftStickY)));
+		input.right			+= Vec2::new(control.right_x.apply(axis(GamepadAxisType::RightStickX)), control.right_y.apply(axis(GamepadAxisType::RightStickY)));
+		input.triggers		+= control.triggers.apply(button_axis(GamepadButtonType::RightTrigger2)) - control.triggers.apply(button_axis(GamepadButtonType::LeftTrigger2));
+
+		input.dpad_up		|= just_pressed(GamepadButtonType::DPadUp);
+		input.dpad_down		|= just_pressed(GamepadButtonType::DPadDown);
+		input.dpad_left		|= just_pressed(GamepadButtonType::DPadLeft);
+		input.dpad_right	|= just_pressed(GamepadButtonType::DPadRight);
+	}
+
+	input.left				= input.left.clamp(Vec2::NEG_ONE, Vec2::ONE);
+	input.right				= input.right.clamp(Vec2::NEG_ONE, Vec2::ONE);
+	input.triggers			= input.triggers.clamp(-1.0, 1.0);
+
+	input
+}
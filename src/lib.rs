@@ -1,10 +1,23 @@
 use bevy :: prelude :: { * };
+use bevy :: transform :: TransformSystem;
+use bevy :: render :: camera :: CameraUpdateSystem;
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
 pub enum CameraMode {
 	Fly,
 	Follow,
 	Reader,
+	/// rotates around a pivot that can be panned and focused on any entity
+	Orbit,
+}
+
+/// How reader camera travels to rows that are further than one and a half screens away
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum JumpMode {
+	/// teleport right to the target
+	Instant,
+	/// zoom out, travel and zoom back in
+	FlyOver,
 }
 
 #[derive(PartialEq, Eq, Clone, Copy)]
@@ -16,14 +29,50 @@ pub enum KeyScroll {
 #[derive(Component, Default, Clone, Copy, Debug)]
 pub struct TextDescriptor {
 	pub glyph_width		: f32,
+	/// height of every row that isn't in RowHeights of the same entity
 	pub glyph_height	: f32,
 	pub rows			: usize,
 	pub columns			: usize,
+	/// row 0 is at the bottom and rows go up like in chats, row offsets are counted from the bottom
+	pub bottom_anchored	: bool,
+}
+
+impl TextDescriptor {
+	/// Direction rows go along Y of target entity, down from the origin unless text is bottom anchored
+	pub fn row_direction(&self) -> f32 {
+		if self.bottom_anchored { 1.0 } else { -1.0 }
+	}
 }
 
 mod reader_camera;
 pub use reader_camera :: ReaderCamera;
 
+mod rows;
+pub use rows :: { RowHeights, RowLayout, RowMap };
+
+mod actions;
+pub use actions :: { * };
+
+mod gamepad;
+pub use gamepad :: { GamepadAxisTuning, GamepadControl, GamepadInput };
+
+mod easing;
+pub use easing :: { * };
+
+mod orbit;
+pub use orbit :: OrbitControl;
+
+mod touch;
+
+mod momentum;
+pub use momentum :: ScrollMomentum;
+
+mod events;
+pub use events :: { * };
+
+mod picking;
+pub use picking :: { * };
+
 mod util;
 mod reader_mode;
 mod systems;
@@ -36,14 +85,36 @@ pub struct ReaderCameraPlugin;
 impl Plugin for ReaderCameraPlugin {
 	fn build(&self, app: &mut App) {
 		app
+			.add_event::<RowScrollRequest>()
+			.add_event::<RowOffsetAck>()
+			.add_event::<RowOffsetChanged>()
+			.add_event::<VisibleRowsChanged>()
+			.add_event::<ReaderCameraCommand>()
+			.add_event::<ModeTransitionFinished>()
+			.add_event::<RowsEdited>()
+
+			.add_system(systems::gamepad_input.in_base_set(CoreSet::PreUpdate).before(systems::reader_mode))
+			.add_system(systems::camera_actions.in_base_set(CoreSet::PreUpdate).before(systems::reader_mode))
 			.add_system(systems::fly_mode_keyboard)
 			.add_system(systems::fly_mode_mouse)
 			.add_system(systems::follow_mode_mouse)
+			.add_system(systems::orbit_mode)
 
 			// PreUpdate because camera transform has to be the same for all systems during update
 			// and because Frustum gets desynced with camera transform and that makes the amount of visible rows jitter
+			// acks come before edits of the same frame, edits are applied with RowMap camera knew before them
+			.add_system(systems::row_offset_ack.in_base_set(CoreSet::PreUpdate).before(systems::rows_edited))
+			.add_system(systems::rows_edited.in_base_set(CoreSet::PreUpdate).before(systems::row_map_sync))
+			.add_system(systems::row_map_sync.in_base_set(CoreSet::PreUpdate).before(systems::reader_mode))
 			.add_system(systems::reader_mode.in_base_set(CoreSet::PreUpdate).in_set(ReaderCameraUpdate))
-			.add_system(systems::calc_frustum_data.in_base_set(CoreSet::PreUpdate).after(systems::reader_mode))
+			.add_system(systems::calc_frustum_data.in_base_set(CoreSet::PreUpdate).in_set(ReaderCameraUpdate).after(systems::reader_mode))
+
+			// modes work with their own transform and blending is applied on top of it at the end of frame
+			.add_system(systems::mode_transition_restore.in_base_set(CoreSet::PreUpdate).before(ReaderCameraUpdate))
+			.add_system(systems::mode_transition.in_base_set(CoreSet::PostUpdate).before(TransformSystem::TransformPropagate))
+			.add_system(systems::projection_transition_restore.in_base_set(CoreSet::PreUpdate).before(systems::mode_transition_restore))
+			// after mode transition to not take dolly offset into presented transform, before camera_system updates projection
+			.add_system(systems::projection_transition.in_base_set(CoreSet::PostUpdate).after(systems::mode_transition).before(TransformSystem::TransformPropagate).before(CameraUpdateSystem))
 		;
 	}
 }
@@ -0,0 +1,109 @@
+use super :: easing :: ease;
+
+// velocity of a gesture is smoothed to not flick with the speed of a single jittery event
+const VELOCITY_SMOOTHING_SECONDS: f32 = 0.025;
+
+/// Scrolling that keeps going after a touchpad or touch screen flick and slows down with friction
+#[derive(Clone, Copy, Debug)]
+pub struct ScrollMomentum {
+	pub enabled					: bool,
+	/// velocity is multiplied by exp(-friction * seconds), higher values stop sooner
+	pub friction				: f32,
+	/// flicks faster than this are clamped
+	pub max_rows_per_second		: f32,
+	/// momentum is dropped once it gets slower than this
+	pub stop_rows_per_second	: f32,
+	/// touchpads don't report when fingers are lifted, so the gesture is considered over after this long without events
+	pub touchpad_release_seconds: f32,
+}
+
+impl Default for ScrollMomentum {
+	fn default() -> Self {
+		Self {
+			enabled					: true,
+			friction				: 4.0,
+			max_rows_per_second		: 400.0,
+			stop_rows_per_second	: 1.0,
+			touchpad_release_seconds: 0.05,
+		}
+	}
+}
+
+/// Velocities are in world units per second, positive is towards the end of text
+#[derive(Default, Clone, Copy, Debug)]
+pub(crate) struct MomentumState {
+	/// smoothed velocity of the gesture that is still going on
+	tracked		: f32,
+	tracking	: bool,
+	/// time since the gesture reported movement last time
+	idle		: f32,
+	/// velocity left after the gesture has ended
+	velocity	: f32,
+}
+
+impl MomentumState {
+	/// Gesture moved content by delta this frame
+	pub(crate) fn track(&mut self, delta: f32, delta_seconds: f32) {
+		self.velocity		= 0.0;
+
+		if delta_seconds > 0.0 {
+			let velocity	= delta / delta_seconds;
+			self.tracked	= if self.tracking { ease(self.tracked, velocity, delta_seconds, VELOCITY_SMOOTHING_SECONDS) } else { velocity };
+		}
+
+		self.tracking		= true;
+		self.idle			= 0.0;
+	}
+
+	/// Gesture didn't report anything this frame, which may be just a gap between touchpad events
+	pub(crate) fn pause(&mut self, settings: &ScrollMomentum, row_height: f32, delta_seconds: f32) {
+		self.idle			+= delta_seconds;
+
+		if self.idle >= settings.touchpad_release_seconds {
+			self.release(settings, row_height);
+		}
+	}
+
+	/// Gesture has ended, whatever velocity it had turns into momentum
+	pub(crate) fn release(&mut self, settings: &ScrollMomentum, row_height: f32) {
+		if self.tracking && settings.enabled {
+			let max_velocity = settings.max_rows_per_second * row_height;
+			self.velocity	= self.tracked.clamp(-max_velocity, max_velocity);
+		}
+
+		self.tracked		= 0.0;
+		self.tracking		= false;
+		self.idle			= 0.0;
+	}
+
+	pub(crate) fn stop(&mut self) {
+		*self				= Self::default();
+	}
+
+	pub(crate) fn velocity(&self) -> f32 {
+		self.velocity
+	}
+
+	pub(crate) fn is_active(&self) -> bool {
+		self.tracking || self.velocity != 0.0
+	}
+
+	/// Distance covered by momentum this frame
+	pub(crate) fn advance(&mut self, settings: &ScrollMomentum, row_height: f32, delta_seconds: f32) -> f32 {
+		if self.velocity == 0.0 {
+			return 0.0
+		}
+
+		// integral of exponentially decaying velocity, so the distance of a flick doesn't depend on frame rate
+		let decay			= (-settings.friction * delta_seconds).exp();
+		let distance		= if settings.friction > 0.0 { self.velocity * (1.0 - decay) / settings.friction } else { self.velocity * delta_seconds };
+
+		self.velocity		*= decay;
+
+		if self.velocity.abs() < settings.stop_rows_per_second * row_height {
+			self.velocity	= 0.0;
+		}
+
+		distance
+	}
+}
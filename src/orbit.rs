@@ -0,0 +1,154 @@
+use bevy :: prelude :: *;
+use bevy :: render :: primitives :: Aabb;
+
+use super :: TextDescriptor;
+use super :: rows :: { RowHeights, RowLayout, RowMap };
+use super :: easing :: { Easing, EasingState };
+use super :: util :: unit_vector_from_yaw_and_pitch;
+
+/// Orbit mode settings. Camera rotates around the pivot with the rotate button held, pans the pivot with the pan button
+/// and changes distance with the wheel. Gamepad: right stick orbits, left stick pans, triggers change distance
+#[derive(Clone, Copy, Debug)]
+pub struct OrbitControl {
+	/// None means mouse movement always orbits
+	pub rotate_button			: Option<MouseButton>,
+	pub pan_button				: MouseButton,
+	/// degrees per pixel of mouse movement
+	pub rotate_sensitivity		: f32,
+	/// fraction of the distance to pivot per pixel of mouse movement
+	pub pan_sensitivity			: f32,
+	/// fraction of the distance per wheel line
+	pub zoom_sensitivity		: f32,
+	pub pitch_min				: f32,
+	pub pitch_max				: f32,
+	pub distance_min			: f32,
+	pub distance_max			: f32,
+	/// free space around focused entity, 1.0 fits its bounds exactly
+	pub focus_margin			: f32,
+	/// gamepad left stick pan speed at full deflection, in distances to pivot per second
+	pub gamepad_pan_per_second	: f32,
+	/// yaw, pitch, distance and pivot are eased towards values set by input
+	pub easing					: Easing,
+}
+
+impl Default for OrbitControl {
+	fn default() -> Self {
+		Self {
+			rotate_button			: Some(MouseButton::Left),
+			pan_button				: MouseButton::Middle,
+			rotate_sensitivity		: 0.3,
+			pan_sensitivity			: 0.002,
+			zoom_sensitivity		: 0.1,
+			pitch_min				: -89.0,
+			pitch_max				: 89.0,
+			distance_min			: 1.0,
+			distance_max			: 500.0,
+			focus_margin			: 1.2,
+			gamepad_pan_per_second	: 1.0,
+			easing					: Easing::Exponential { seconds: 0.1 },
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct OrbitState {
+	pub(crate) pivot				: Vec3,
+	/// yaw and pitch in degrees, distance to pivot
+	pub(crate) yaw_pitch_distance	: Vec3,
+	pub(crate) target_pivot			: Vec3,
+	pub(crate) target_yaw_pitch_distance: Vec3,
+	pub(crate) pivot_easing			: EasingState,
+	pub(crate) yaw_pitch_distance_easing: EasingState,
+}
+
+impl OrbitState {
+	/// Orbit around pivot from given camera position without moving the camera
+	pub(crate) fn look_from(&mut self, translation: Vec3, pivot: Vec3, control: &OrbitControl) {
+		let offset			= translation - pivot;
+		let direction		= offset.normalize_or_zero();
+
+		// inverse of unit_vector_from_yaw_and_pitch
+		let yaw				= direction.x.atan2(direction.z).to_degrees();
+		let pitch			= direction.y.clamp(-1.0, 1.0).asin().to_degrees().clamp(control.pitch_min, control.pitch_max);
+		let distance		= offset.length().clamp(control.distance_min, control.distance_max);
+
+		self.pivot			= pivot;
+		self.target_pivot	= pivot;
+		self.yaw_pitch_distance = Vec3::new(yaw, pitch, distance);
+		self.target_yaw_pitch_distance = self.yaw_pitch_distance;
+		self.pivot_easing.reset();
+		self.yaw_pitch_distance_easing.reset();
+	}
+
+	pub(crate) fn rotate(&mut self, yaw: f32, pitch: f32, control: &OrbitControl) {
+		let target			= &mut self.target_yaw_pitch_distance;
+		target.x			+= yaw;
+		target.y			= (target.y + pitch).clamp(control.pitch_min, control.pitch_max);
+	}
+
+	/// Moves pivot in camera plane, offset is in fractions of distance
+	pub(crate) fn pan(&mut self, offset: Vec2, rotation: Quat) {
+		let distance		= self.target_yaw_pitch_distance.z;
+		self.target_pivot	+= (rotation * Vec3::new(offset.x, offset.y, 0.0)) * distance;
+	}
+
+	/// Multiplies distance to pivot
+	pub(crate) fn zoom(&mut self, scale: f32, control: &OrbitControl) {
+		let target			= &mut self.target_yaw_pitch_distance;
+		target.z			= (target.z * scale).clamp(control.distance_min, control.distance_max);
+	}
+
+	pub(crate) fn focus(&mut self, center: Vec3, radius: f32, fov: f32, control: &OrbitControl) {
+		let distance		= radius * control.focus_margin / (fov / 2.0).sin().max(f32::EPSILON);
+
+		self.target_pivot	= center;
+		self.target_yaw_pitch_distance.z = distance.clamp(control.distance_min, control.distance_max);
+	}
+
+	pub(crate) fn update(&mut self, easing: Easing, delta_seconds: f32) {
+		// shortest way around for yaw so the camera doesn't spin back after many turns
+		let yaw_offset		= (self.target_yaw_pitch_distance.x - self.yaw_pitch_distance.x + 180.0).rem_euclid(360.0) - 180.0;
+		let target			= Vec3::new(self.yaw_pitch_distance.x + yaw_offset, self.target_yaw_pitch_distance.y, self.target_yaw_pitch_distance.z);
+		self.target_yaw_pitch_distance.x = target.x;
+
+		self.yaw_pitch_distance = easing.step(self.yaw_pitch_distance, target, delta_seconds, &mut self.yaw_pitch_distance_easing);
+		self.pivot			= easing.step(self.pivot, self.target_pivot, delta_seconds, &mut self.pivot_easing);
+	}
+
+	pub(crate) fn transform(&self) -> Transform {
+		let yaw				= self.yaw_pitch_distance.x.to_radians();
+		let pitch			= self.yaw_pitch_distance.y.to_radians();
+
+		Transform {
+			translation		: self.pivot + self.yaw_pitch_distance.z * unit_vector_from_yaw_and_pitch(yaw, pitch),
+			rotation		: Quat::from_axis_angle(Vec3::Y, yaw) * Quat::from_axis_angle(-Vec3::X, pitch),
+			..default()
+		}
+	}
+}
+
+/// Center and radius of the sphere around entity: text block for text, mesh bounds if there are any, just its position otherwise
+pub(crate) fn entity_bounds(
+	transform		: &GlobalTransform,
+	aabb			: Option<&Aabb>,
+	text_descriptor	: Option<&TextDescriptor>,
+	row_heights		: Option<&RowHeights>,
+	row_map			: Option<&RowMap>,
+) -> (Vec3, f32) {
+	let (center, half_extents) = if let Some(text) = text_descriptor {
+		// row heights are per visual row, folds and wrapping change how many there are
+		let rows	= row_map.map_or(text.rows as u32, |row_map| row_map.to_visual(text.rows as u32));
+		let height	= RowLayout::new(text, row_heights).top(rows as f32);
+		let size	= Vec3::new(text.columns as f32 * text.glyph_width, height, 0.0);
+		// text goes right and down from the origin, up if it's bottom anchored
+		(Vec3::new(size.x, size.y * text.row_direction(), 0.0) / 2.0, size / 2.0)
+	} else if let Some(aabb) = aabb {
+		(Vec3::from(aabb.center), Vec3::from(aabb.half_extents))
+	} else {
+		(Vec3::ZERO, Vec3::ZERO)
+	};
+
+	let (scale, _, _) = transform.to_scale_rotation_translation();
+
+	(transform.transform_point(center), (half_extents * scale).length())
+}
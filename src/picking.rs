@@ -0,0 +1,179 @@
+use bevy :: {
+	prelude	:: { * },
+	ecs		:: system :: SystemParam,
+	window	:: PrimaryWindow,
+};
+
+use super :: TextDescriptor;
+use super :: rows :: { RowHeights, RowLayout, RowMap };
+use super :: reader_camera :: ReaderCamera;
+use super :: util :: { GLYPH_Z_OFFSET, viewport_origin };
+
+// Text is laid out the same way reader mode expects it: row 0 starts at the origin of target entity,
+// rows go down along -Y and columns go right along +X, so glyph (row, column) covers
+// x in [column * glyph_width, (column + 1) * glyph_width) and y in (-(row + 1) * glyph_height, -row * glyph_height]
+// unless RowHeights gives rows their own heights. Bottom anchored text is the same mirrored along Y, rows go up
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GlyphPick {
+	/// 0.5 is the middle of the first row, not clamped to text
+	pub row_fract		: f32,
+	/// 0.5 is the middle of the first column, not clamped to text
+	pub column_fract	: f32,
+	/// visual row under cursor clamped to text
+	pub row				: usize,
+	/// column under cursor clamped to text
+	pub column			: usize,
+	/// false if cursor is outside of text and row/column got clamped
+	pub inside_text		: bool,
+}
+
+// reader mode doesn't take scale of target entity into account so we don't either
+fn target_plane_transform(target_transform: &Transform) -> Transform {
+	Transform {
+		translation	: target_transform.translation,
+		rotation	: target_transform.rotation,
+		scale		: Vec3::ONE,
+	}
+}
+
+/// (column, row) in fractions of glyphs for a point on the text surface
+pub fn world_to_glyph(
+	world_position		: Vec3,
+	target_transform	: &Transform,
+	text_descriptor		: &TextDescriptor,
+	row_heights			: Option<&RowHeights>,
+) -> Vec2 {
+	let local = target_plane_transform(target_transform).compute_matrix().inverse().transform_point3(world_position);
+
+	Vec2::new(
+		local.x / text_descriptor.glyph_width,
+		RowLayout::new(text_descriptor, row_heights).row_at(local.y * text_descriptor.row_direction()),
+	)
+}
+
+/// Point on the text surface for (column, row) given in fractions of glyphs
+pub fn glyph_to_world(
+	row					: f32,
+	column				: f32,
+	target_transform	: &Transform,
+	text_descriptor		: &TextDescriptor,
+	row_heights			: Option<&RowHeights>,
+) -> Vec3 {
+	let local = Vec3::new(
+		column * text_descriptor.glyph_width,
+		RowLayout::new(text_descriptor, row_heights).top(row) * text_descriptor.row_direction(),
+		GLYPH_Z_OFFSET,
+	);
+
+	target_plane_transform(target_transform).transform_point(local)
+}
+
+/// Glyph under given viewport position (logical pixels, bottom left origin like in Camera::viewport_to_world).
+/// Rows are visual, row_map gives their amount and RowMap::to_logical turns picked row into app's row
+pub fn viewport_to_glyph(
+	viewport_position	: Vec2,
+	camera				: &Camera,
+	camera_transform	: &Transform,
+	target_transform	: &Transform,
+	text_descriptor		: &TextDescriptor,
+	row_heights			: Option<&RowHeights>,
+	row_map				: Option<&RowMap>,
+) -> Option<GlyphPick> {
+	let ray				= camera.viewport_to_world(&GlobalTransform::from(*camera_transform), viewport_position)?;
+
+	let plane_normal	= target_transform.rotation * Vec3::Z;
+	let plane_origin	= target_transform.translation + plane_normal * GLYPH_Z_OFFSET;
+	let distance		= ray.intersect_plane(plane_origin, plane_normal)?;
+
+	let glyph			= world_to_glyph(ray.get_point(distance), target_transform, text_descriptor, row_heights);
+
+	let rows			= row_map.map_or(text_descriptor.rows, |row_map| row_map.to_visual(text_descriptor.rows as u32) as usize);
+	let row_max			= rows.max(1) - 1;
+	let column_max		= text_descriptor.columns.max(1) - 1;
+
+	let row				= glyph.y.floor();
+	let column			= glyph.x.floor();
+
+	let inside_text		= row >= 0.0 && column >= 0.0 && row <= row_max as f32 && column <= column_max as f32;
+
+	Some(GlyphPick {
+		row_fract		: glyph.y,
+		column_fract	: glyph.x,
+		row				: (row.max(0.0) as usize).min(row_max),
+		column			: (column.max(0.0) as usize).min(column_max),
+		inside_text,
+	})
+}
+
+/// Viewport position (logical pixels, bottom left origin) of given point on text, use 0.5 fractions for glyph centers
+pub fn glyph_to_viewport(
+	row					: f32,
+	column				: f32,
+	camera				: &Camera,
+	camera_transform	: &Transform,
+	target_transform	: &Transform,
+	text_descriptor		: &TextDescriptor,
+	row_heights			: Option<&RowHeights>,
+) -> Option<Vec2> {
+	let world_position = glyph_to_world(row, column, target_transform, text_descriptor, row_heights);
+
+	camera.world_to_viewport(&GlobalTransform::from(*camera_transform), world_position)
+}
+
+/// Picking for reader cameras in window coordinates (logical pixels, bottom left origin like Window::cursor_position)
+#[derive(SystemParam)]
+pub struct ReaderPicking<'w, 's> {
+	q_camera			: Query<'w, 's, (&'static ReaderCamera, &'static Camera)>,
+	q_text_descriptor	: Query<'w, 's, (&'static TextDescriptor, Option<&'static RowHeights>)>,
+	q_transform			: Query<'w, 's, &'static Transform>,
+	q_window			: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+}
+
+impl<'w, 's> ReaderPicking<'w, 's> {
+	/// Glyph under given window position (e.g. Window::cursor_position) for given ReaderCamera entity
+	pub fn glyph_at(&self, camera_entity: Entity, window_position: Vec2) -> Option<GlyphPick> {
+		let (reader_camera, camera) = self.q_camera.get(camera_entity).ok()?;
+		let target_entity	= reader_camera.target_entity?;
+
+		let viewport_position = window_position - viewport_origin(self.q_window.get_single().ok()?, camera)?;
+
+		let (text_descriptor, row_heights) = self.q_text_descriptor.get(target_entity).ok()?;
+
+		viewport_to_glyph(
+			viewport_position,
+			camera,
+			self.q_transform.get(camera_entity).ok()?,
+			self.q_transform.get(target_entity).ok()?,
+			text_descriptor,
+			row_heights,
+			Some(&reader_camera.row_map),
+		)
+	}
+
+	/// Glyph under the cursor of primary window
+	pub fn glyph_at_cursor(&self, camera_entity: Entity) -> Option<GlyphPick> {
+		let cursor = self.q_window.get_single().ok()?.cursor_position()?;
+		self.glyph_at(camera_entity, cursor)
+	}
+
+	/// Window position of given point on text, use 0.5 fractions for glyph centers
+	pub fn window_position(&self, camera_entity: Entity, row: f32, column: f32) -> Option<Vec2> {
+		let (reader_camera, camera) = self.q_camera.get(camera_entity).ok()?;
+		let target_entity	= reader_camera.target_entity?;
+
+		let (text_descriptor, row_heights) = self.q_text_descriptor.get(target_entity).ok()?;
+
+		let viewport_position = glyph_to_viewport(
+			row,
+			column,
+			camera,
+			self.q_transform.get(camera_entity).ok()?,
+			self.q_transform.get(target_entity).ok()?,
+			text_descriptor,
+			row_heights,
+		)?;
+
+		Some(viewport_position + viewport_origin(self.q_window.get_single().ok()?, camera)?)
+	}
+}
@@ -1,6 +1,16 @@
 use bevy :: prelude :: *;
 
 use super :: CameraMode;
+use super :: JumpMode;
+use super :: actions :: ActionMap;
+use super :: gamepad :: { GamepadControl, GamepadInput };
+use super :: touch :: { TouchGesture, TouchState };
+use super :: momentum :: { MomentumState, ScrollMomentum };
+use super :: easing :: { Easing, EasingState };
+use super :: orbit :: { OrbitControl, OrbitState };
+use super :: util :: GLYPH_Z_OFFSET;
+use super :: rows :: RowMap;
+use super :: events :: RowsEdited;
 
 #[derive(PartialEq, Eq)]
 pub(crate) enum AwakeState {
@@ -8,12 +18,47 @@ pub(crate) enum AwakeState {
 	Awake,
 }
 
+pub(crate) struct Jump {
+	pub(crate) from			: Vec3,
+	pub(crate) elapsed		: f32,
+	pub(crate) duration		: f32,
+	pub(crate) zoom_out		: f32,
+}
+
+pub(crate) struct ModeTransition {
+	pub(crate) from			: Transform,
+	/// transform of the new mode without blending
+	pub(crate) to			: Transform,
+	pub(crate) elapsed		: f32,
+	pub(crate) duration		: f32,
+}
+
+pub(crate) struct ProjectionTransition {
+	/// projection we are switching to, modes keep changing it during transition
+	pub(crate) to			: Projection,
+	/// perspective end of the transition
+	pub(crate) perspective	: PerspectiveProjection,
+	/// offset along view direction added to presented transform
+	pub(crate) dolly		: Vec3,
+	pub(crate) elapsed		: f32,
+	pub(crate) duration		: f32,
+}
+
 #[derive(Component)]
 pub struct ReaderCamera {
-	///
+	/// fly mode acceleration in units per second squared
 	pub accel: f32,
-	///
+	/// fly mode speed limit in units per second
 	pub max_speed: f32,
+	/// fly mode velocity loss per second, sets how fast camera stops without input
+	pub drag: f32,
+	/// fly mode acceleration and speed limit multiplier while Sprint is held
+	pub sprint_multiplier: f32,
+	/// fly mode acceleration and speed limit multiplier, changed with mouse wheel
+	pub speed_scale: f32,
+	/// fly mode hides and locks cursor while right mouse button is held, mouse looks around only then
+	pub grab_cursor: bool,
+	pub(crate) cursor_grabbed: bool,
 	///
 	pub sensitivity : f32,
 	///
@@ -22,8 +67,12 @@ pub struct ReaderCamera {
 	pub mouse_scroll_sensitivity: f32,
 	///
 	pub wheel_scroll_sensitivity: f32,
+	/// horizontal wheel and shift + wheel
+	pub wheel_swipe_sensitivity: f32,
 	///
 	pub zoom_sensitivity: f32,
+	/// scrolling speed while ScrollLineUp/ScrollLineDown is held
+	pub key_scroll_rows_per_second: f32,
 	///
 	pub pitch_max: f32,
 
@@ -31,18 +80,43 @@ pub struct ReaderCamera {
 	pub mouse_scroll_easing_seconds: f32,
 	///
 	pub swipe_easing_seconds: f32,
-	///
-	pub translation_easing_scroll_seconds: f32,
-	///
-	pub translation_easing_seconds: f32,
-	///
-	pub rotation_easing_seconds: f32,
-	///
-	pub zoom_easing_seconds: f32,
-	///
-	pub lean_easing_seconds: f32,
-	///
-	pub lean_reset_easing_seconds: f32,
+	/// translation while scroll keys are held
+	pub translation_scroll_easing: Easing,
+	///
+	pub translation_easing: Easing,
+	///
+	pub rotation_easing: Easing,
+	///
+	pub zoom_easing: Easing,
+	/// leaning towards scroll direction
+	pub lean_easing: Easing,
+	/// leaning back once scrolling stops
+	pub lean_reset_easing: Easing,
+	/// snapping back to precise row and column after scrolling stops
+	pub snap_easing_seconds: f32,
+	/// switching between translation_easing and translation_scroll_easing
+	pub translation_easing_blend_seconds: f32,
+	/// no leaning, no fly-over jumps, no scroll momentum and reduced_motion_easing instead of every other easing
+	pub reduced_motion: bool,
+	/// Linear with zero seconds snaps right to the target
+	pub reduced_motion_easing: Easing,
+	///
+	pub jump_mode: JumpMode,
+	/// duration of the longest fly-over jump, shorter jumps take less
+	pub jump_max_seconds: f32,
+	/// how far camera zooms out in the middle of fly-over jump, multiplier of current zoom
+	pub jump_zoom_out: f32,
+	pub(crate) jump: Option<Jump>,
+	/// Orbit mode input, limits and easing
+	pub orbit: OrbitControl,
+	pub(crate) orbit_state: OrbitState,
+	/// how long camera blends from one mode to another
+	pub mode_transition_seconds: f32,
+	pub(crate) mode_transition: Option<ModeTransition>,
+	/// mode we are switching from, transition starts at the end of frame
+	pub(crate) mode_transition_requested: Option<CameraMode>,
+	/// transform at the end of last frame
+	pub(crate) transform_presented: Option<Transform>,
 
 	///
 	pub pitch: f32,
@@ -52,26 +126,64 @@ pub struct ReaderCamera {
 	pub zoom: f32,
 	///
 	pub target_zoom: f32,
+	/// zoom set by CameraAction::ZoomReset
+	pub zoom_default: f32,
 	///
 	pub scroll: f32,
 	///
 	pub swipe: f32,
-	///
+	/// column in the middle of viewport, set to the center of text on the first frame in Reader mode
 	pub column: usize,
 	///
 	pub target_row_prev : f32,
 	///
 	pub row_constant_offset: f32,
-	///
+	/// row offsets are visual rows internally and logical ones in public api, see RowMap
 	pub(crate) row_offset_app: u32,
 	///
 	pub(crate) row_offset_camera: u32,
 	///
 	pub(crate) row_offset_delta: i32,
+	/// part of row_offset_delta that was already sent to app with RowScrollRequest
+	pub(crate) row_offset_delta_sent: i32,
+	/// copy of RowMap of target entity, identity without one
+	pub(crate) row_map: RowMap,
+	/// entity row_map was copied from
+	pub(crate) row_map_source: Option<Entity>,
+	/// visual row at the top edge of viewport with fraction, row_offset_camera before rounding
+	pub(crate) row_anchor: f32,
+	/// logical row and visual rows into it to put back to the top edge after RowsEdited
+	pub(crate) row_anchor_pending: Option<(u32, f32)>,
+	/// text cursor position (row, column) to keep in view, see caret_follow
+	pub caret: Option<(u32, usize)>,
+	/// caret position camera already reacted to
+	pub(crate) caret_followed: Option<(u32, usize)>,
+	/// keep caret in view when it moves
+	pub caret_follow: bool,
+	/// rows between caret and top edge of the viewport before camera starts scrolling
+	pub scrolloff_top: u32,
+	/// rows between caret and bottom edge of the viewport before camera starts scrolling
+	pub scrolloff_bottom: u32,
+	/// columns between caret and left edge of the viewport before camera starts scrolling
+	pub scrolloff_left: usize,
+	/// columns between caret and right edge of the viewport before camera starts scrolling
+	pub scrolloff_right: usize,
+	/// rows from previous page that stay visible after PageUp/PageDown to keep context
+	pub page_overlap_rows: u32,
+	/// stick to the end of text when it grows like tail -f, scrolling away from the end pauses it until camera is back there
+	pub tail_follow: bool,
+	/// viewport is at the end of text and follows new rows, see tail_follow
+	pub(crate) tail_following: bool,
+	/// amount of visual rows text had last frame
+	pub(crate) tail_rows_prev: u32,
 	///
 	pub visible_rows: f32,
 	///
 	pub visible_rows_target: Option<f32>,
+	/// height of text plane area in viewport, visible rows can differ in height so they are counted from it
+	pub visible_height: f32,
+	/// visible_height after zoom is over
+	pub visible_height_target: Option<f32>,
 	///
 	pub visible_columns: f32,
 	///
@@ -84,8 +196,12 @@ pub struct ReaderCamera {
 	pub x_left: f32,
 	///
 	pub x_right: f32,
-	///
-	pub translation_easing_current: f32,
+	/// 0.0 is translation_easing, 1.0 is translation_scroll_easing
+	pub(crate) translation_scroll_blend: f32,
+	pub(crate) translation_easing_state: EasingState,
+	pub(crate) rotation_easing_state: EasingState,
+	pub(crate) zoom_easing_state: EasingState,
+	pub(crate) lean_easing_state: EasingState,
 
 	///
 	pub swipe_accum: f32,
@@ -96,29 +212,32 @@ pub struct ReaderCamera {
 	///
 	pub target_rotation: Quat,
 
-	/// 
+	/// fly mode velocity in units per second
 	pub velocity: Vec3,
 
-	///
-	pub key_forward: KeyCode,
-	///
-	pub key_backward: KeyCode,
-	///
-	pub key_left: KeyCode,
-	///
-	pub key_right: KeyCode,
-	///
-	pub key_up: KeyCode,
-	///
-	pub key_down: KeyCode,
-	///
-	pub key_perspective: KeyCode,
-	///
-	pub mod_perspective: Option<KeyCode>,
-	///
+	/// input bindings for every mode
+	pub actions: ActionMap,
+	///
+	pub gamepad: GamepadControl,
+	/// gamepad state for current frame, zero if camera has no input focus
+	pub(crate) gamepad_input: GamepadInput,
+	/// scrolling after touchpad and touch screen flicks
+	pub momentum: ScrollMomentum,
+	pub(crate) momentum_state: MomentumState,
+	pub(crate) touch_state: TouchState,
+	pub(crate) touch_gesture: TouchGesture,
+	/// perspective or orthographic projection, change it with set_perspective to animate the switch
 	pub perspective: bool,
+	/// how long dolly zoom between perspective and orthographic projection takes
+	pub projection_transition_seconds: f32,
+	pub(crate) projection_transition: Option<ProjectionTransition>,
+	/// projection switch starts at the end of frame
+	pub(crate) projection_transition_requested: bool,
 	///
 
+	/// camera reacts to keyboard, pointer input goes to the camera under the cursor. On for every camera by default,
+	/// with several cameras keep it on only for the focused one or all of them react to the same keys
+	pub input_focus: bool,
 	///
 	pub invert_y: bool,
 	///
@@ -160,28 +279,50 @@ pub struct ReaderCamera {
 impl Default for ReaderCamera {
 	fn default() -> Self {
 		Self {
-			accel								: 1.5,
-			max_speed							: 100.0,
+			accel								: 20.0,
+			max_speed							: 5.0,
+			drag								: 4.0,
+			sprint_multiplier					: 3.0,
+			speed_scale							: 1.0,
+			grab_cursor							: false,
+			cursor_grabbed						: false,
 			sensitivity							: 3.0,
-			swipe_sensitivity					: 0.0,
+			swipe_sensitivity					: 1.0,
 			mouse_scroll_sensitivity			: 1.0,
 			wheel_scroll_sensitivity			: 0.3,
+			wheel_swipe_sensitivity				: 0.3,
 			zoom_sensitivity					: 1.0,
+			key_scroll_rows_per_second			: 20.0,
 			pitch_max							: 1.0,
 
 			mouse_scroll_easing_seconds			: 5.0,
 			swipe_easing_seconds				: 6.0,
-			translation_easing_seconds			: 0.05,
-			translation_easing_scroll_seconds	: 0.15,
-			rotation_easing_seconds				: 0.1,
-			zoom_easing_seconds					: 0.01,
-			lean_easing_seconds					: 0.1,
-			lean_reset_easing_seconds			: 0.05,
+			translation_easing					: Easing::Exponential { seconds: 0.05 },
+			translation_scroll_easing			: Easing::Exponential { seconds: 0.15 },
+			rotation_easing						: Easing::Exponential { seconds: 0.1 },
+			zoom_easing							: Easing::Exponential { seconds: 0.01 },
+			lean_easing							: Easing::Exponential { seconds: 0.1 },
+			lean_reset_easing					: Easing::Exponential { seconds: 0.05 },
+			reduced_motion						: false,
+			reduced_motion_easing				: Easing::Linear { seconds: 0.05 },
+			snap_easing_seconds					: 0.16,
+			translation_easing_blend_seconds	: 0.16,
+			jump_mode							: JumpMode::FlyOver,
+			jump_max_seconds					: 0.8,
+			jump_zoom_out						: 2.0,
+			jump								: None,
+			orbit								: OrbitControl::default(),
+			orbit_state							: OrbitState::default(),
+			mode_transition_seconds				: 0.6,
+			mode_transition						: None,
+			mode_transition_requested			: None,
+			transform_presented					: None,
 
 			pitch								: 0.0,
 			yaw									: 0.0,
 			zoom								: 7.0,
 			target_zoom							: 7.0,
+			zoom_default						: 7.0,
 			scroll								: 0.0,
 			swipe								: 0.0,
 			column								: 51,
@@ -190,30 +331,54 @@ impl Default for ReaderCamera {
 			row_offset_app						: 0,
 			row_offset_camera					: 0,
 			row_offset_delta					: 0,
+			row_offset_delta_sent				: 0,
+			row_map								: RowMap::default(),
+			row_map_source						: None,
+			row_anchor							: 0.0,
+			row_anchor_pending					: None,
+			caret								: None,
+			caret_followed						: None,
+			caret_follow						: true,
+			scrolloff_top						: 3,
+			scrolloff_bottom					: 3,
+			scrolloff_left						: 5,
+			scrolloff_right						: 5,
+			tail_follow							: false,
+			tail_following						: false,
+			tail_rows_prev						: 0,
+			page_overlap_rows					: 2,
 			visible_rows						: 40.0,
 			visible_rows_target					: None,
+			visible_height						: 0.0,
+			visible_height_target				: None,
 			visible_columns						: 80.0,
 			visible_columns_target				: None,
 			y_top								: 0.0,
 			y_bottom							: 0.0,
 			x_left								: 0.0,
 			x_right								: 0.0,
-			translation_easing_current			: 0.05,
+			translation_scroll_blend			: 0.0,
+			translation_easing_state			: EasingState::default(),
+			rotation_easing_state				: EasingState::default(),
+			zoom_easing_state					: EasingState::default(),
+			lean_easing_state					: EasingState::default(),
 
 			swipe_accum							: 0.0,
 			scroll_accum						: 0.0,
 			target_translation					: Vec3::ZERO,
 			target_rotation						: Quat::IDENTITY,
 			velocity							: Vec3::ZERO,
-			key_forward							: KeyCode::W,
-			key_backward						: KeyCode::S,
-			key_left							: KeyCode::A,
-			key_right							: KeyCode::D,
-			key_up								: KeyCode::Space,
-			key_down							: KeyCode::LShift,
-			key_perspective						: KeyCode::Return,
-			mod_perspective						: Some(KeyCode::LControl),
+			actions								: ActionMap::default(),
+			gamepad								: GamepadControl::default(),
+			gamepad_input						: GamepadInput::default(),
+			momentum							: ScrollMomentum::default(),
+			momentum_state						: MomentumState::default(),
+			touch_state							: TouchState::default(),
+			touch_gesture						: TouchGesture::default(),
 			perspective							: true,
+			projection_transition_seconds		: 0.5,
+			projection_transition				: None,
+			projection_transition_requested		: false,
 
 			enabled_scroll						: true,
 			enabled_translation					: false,
@@ -227,6 +392,7 @@ impl Default for ReaderCamera {
 
 			mode								: CameraMode::Fly,
 
+			input_focus							: true,
 			invert_y							: false,
 			pitch_changed						: false,
 			awake								: AwakeState::Awake,
@@ -238,10 +404,24 @@ impl Default for ReaderCamera {
 }
 
 impl ReaderCamera {
+	/// Switches mode with a transition from the current transform, see mode_transition_seconds
 	pub fn set_mode(&mut self, mode: CameraMode) {
+		if mode != self.mode && self.mode_transition_requested.is_none() {
+			self.mode_transition_requested = Some(self.mode);
+		}
+
 		self.mode = mode;
 	}
 
+	/// Switches projection with a dolly zoom that keeps the text plane the same size, see projection_transition_seconds
+	pub fn set_perspective(&mut self, perspective: bool) {
+		if perspective != self.perspective {
+			self.projection_transition_requested = !self.projection_transition_requested;
+		}
+
+		self.perspective = perspective;
+	}
+
 	pub fn set_mode_wrestrictions(
 		&mut self,
 		camera_mode		: CameraMode,
@@ -250,7 +430,7 @@ impl ReaderCamera {
 		zoom			: bool,
 		scroll			: bool,
 	) {
-		self.mode = camera_mode;
+		self.set_mode(camera_mode);
 		self.set_restrictions(translation, rotation, zoom, scroll);
 	}
 
@@ -288,28 +468,113 @@ impl ReaderCamera {
 		self.default_enabled_scroll = false;
 	}
 
+	/// Logical row at the top edge of viewport on the app side
 	pub fn set_row_offset_in(&mut self, row_offset_in: u32) {
-		self.row_offset_app = row_offset_in;
+		self.row_offset_app = self.row_map.to_visual(row_offset_in);
 	}
 
 	pub fn row_offset_in(&self) -> u32 {
-		self.row_offset_app
+		self.row_map.to_logical(self.row_offset_app)
 	}
 
+	/// Logical row at the top edge of viewport the camera is looking at
 	pub fn row_offset_out(&self) -> u32 {
-		self.row_offset_camera
+		self.row_map.to_logical(self.row_offset_camera)
 	}
 
+	/// Logical rows between row_offset_in and where the camera is going
 	pub fn row_offset_delta(&self) -> i32 {
-		self.row_offset_delta
+		self.logical_rows_between(self.row_offset_app as i64, self.row_offset_app as i64 + self.row_offset_delta as i64)
 	}
 
+	/// Polling alternative to RowScrollRequest/RowOffsetAck events, don't mix the two
 	pub fn row_offset_delta_apply(&mut self) -> i32 {
-		let cache = self.row_offset_delta;
+		let cache = self.row_offset_delta();
 		self.row_offset_delta = 0;
+		self.row_offset_delta_sent = 0;
 		cache
 	}
 
+	/// App has scrolled by every requested row so far and now has row_offset_in
+	pub fn acknowledge_row_offset(&mut self, row_offset_in: u32) {
+		let requested = (self.row_offset_app as i64 + self.row_offset_delta_sent as i64).max(0) as u32;
+
+		self.row_offset_app = self.row_map.to_visual(row_offset_in);
+		self.row_offset_delta -= self.row_offset_delta_sent;
+		self.row_offset_delta_sent = 0;
+
+		// app scrolls by whole logical rows, visual rows of wrapped row we asked for are kept
+		if self.row_map.to_logical(requested) == row_offset_in {
+			self.row_offset_delta += requested as i32 - self.row_offset_app as i32;
+		}
+	}
+
+	/// Returns amount of logical rows that app hasn't been asked to scroll by yet and marks them as sent
+	pub(crate) fn row_offset_delta_unsent(&mut self) -> i32 {
+		let from = self.row_offset_app as i64 + self.row_offset_delta_sent as i64;
+		let to = self.row_offset_app as i64 + self.row_offset_delta as i64;
+		self.row_offset_delta_sent = self.row_offset_delta;
+		self.logical_rows_between(from, to)
+	}
+
+	/// Keeps the same logical rows when visual rows they map to change
+	pub(crate) fn set_row_map(&mut self, row_map: RowMap) {
+		let row_offset_app		= self.row_map.to_logical(self.row_offset_app);
+		let row_offset_camera	= self.row_map.to_logical(self.row_offset_camera);
+
+		self.row_map			= row_map;
+
+		self.row_offset_app		= self.row_map.to_visual(row_offset_app);
+		self.row_offset_camera	= self.row_map.to_visual(row_offset_camera);
+	}
+
+	/// Moves row offsets and caret along with the rows app has edited, camera itself moves in reader_mode
+	pub(crate) fn edit_rows(&mut self, edit: &RowsEdited) {
+		let row_offset_app		= edit.anchored(self.row_map.to_logical(self.row_offset_app));
+		let row_offset_camera	= edit.anchored(self.row_map.to_logical(self.row_offset_camera));
+
+		// several edits in one frame keep moving the same anchor
+		let (anchor, in_row)	= self.row_anchor_pending.unwrap_or_else(|| {
+			let anchor			= self.row_map.to_logical(self.row_anchor.max(0.0) as u32);
+			(anchor, self.row_anchor - self.row_map.to_visual(anchor) as f32)
+		});
+
+		// removed row takes its place in viewport with it
+		let removed				= edit.row..edit.row.saturating_add(edit.removed);
+		let in_row				= if removed.contains(&anchor) { 0.0 } else { in_row };
+
+		if self.mode == CameraMode::Reader {
+			self.row_anchor_pending = Some((edit.anchored(anchor), in_row));
+		}
+
+		// new rows take one visual row until app sends its RowMap
+		let row_offset_app_prev	= self.row_offset_app;
+		self.row_map.replace(removed, std::iter::repeat_n(1, edit.inserted as usize));
+
+		self.row_offset_app		= self.row_map.to_visual(row_offset_app);
+		self.row_offset_camera	= self.row_map.to_visual(row_offset_camera);
+		self.target_row_prev	+= self.row_offset_app as f32 - row_offset_app_prev as f32;
+
+		let anchored			= |caret: Option<(u32, usize)>| caret.map(|(row, column)| (edit.anchored(row), column));
+		self.caret				= anchored(self.caret);
+		self.caret_followed		= anchored(self.caret_followed);
+	}
+
+	fn logical_rows_between(&self, from: i64, to: i64) -> i32 {
+		// rows before the start of text are one to one
+		let logical = |row: i64| if row < 0 { row } else { self.row_map.to_logical(row as u32) as i64 };
+		(logical(to) - logical(from)) as i32
+	}
+
+	pub fn set_caret(&mut self, row: u32, column: usize) {
+		self.caret = Some((row, column));
+	}
+
+	pub fn clear_caret(&mut self) {
+		self.caret = None;
+		self.caret_followed = None;
+	}
+
 	pub fn put_to_sleep(&mut self) {
 		self.awake = AwakeState::Dormant;
 	}
@@ -338,6 +603,80 @@ impl ReaderCamera {
 		(self.target_zoom - self.zoom).abs() >= 0.001 // TODO: magic numbers bad, settings or constants good
 	}
 
+	/// Given channel easing or reduced_motion_easing if reduced motion is on
+	pub fn easing(&self, easing: Easing) -> Easing {
+		if self.reduced_motion { self.reduced_motion_easing } else { easing }
+	}
+
+	/// Makes current mode continue from given transform instead of snapping to where it was before
+	pub(crate) fn init_mode_from(&mut self, transform: &Transform, target_translation: Option<Vec3>) {
+		match self.mode {
+			CameraMode::Fly => {
+				let (yaw, pitch_x, _) = transform.rotation.to_euler(EulerRot::YXZ);
+				// fly rotation is yaw around Y and pitch around -X
+				self.yaw				= yaw.to_degrees();
+				self.pitch				= -pitch_x.to_degrees();
+				self.target_translation	= transform.translation;
+				self.velocity			= Vec3::ZERO;
+			},
+			CameraMode::Follow => {
+				let Some(target_translation) = target_translation else { return };
+
+				// follow orbits around target too, just with its own limits
+				let limits				= OrbitControl { pitch_min: -89.0, pitch_max: 89.9, distance_min: 1.0, distance_max: 100.0, ..self.orbit };
+				let mut state			= OrbitState::default();
+				state.look_from(transform.translation, target_translation, &limits);
+
+				self.yaw				= state.yaw_pitch_distance.x;
+				self.pitch				= state.yaw_pitch_distance.y;
+				self.zoom				= state.yaw_pitch_distance.z;
+			},
+			CameraMode::Orbit => {
+				// orbit around the point camera looks at, as deep as target is or zoom distance away without target
+				let depth				= target_translation.map_or(self.zoom, |target| (target - transform.translation).dot(transform.forward()));
+				let depth				= if depth > 0.0 { depth } else { self.zoom };
+				let pivot				= transform.translation + transform.forward() * depth;
+				let control				= self.orbit;
+				self.orbit_state.look_from(transform.translation, pivot, &control);
+			},
+			CameraMode::Reader => {
+				self.pitch				= 0.0;
+				self.yaw				= 0.0;
+				self.jump				= None;
+				self.translation_easing_state.reset();
+				self.rotation_easing_state.reset();
+			},
+		}
+	}
+
+	/// Distance along view direction to the plane that keeps its size during projection transition
+	pub(crate) fn focus_distance(&self, transform: &Transform, target_translation: Option<Vec3>) -> f32 {
+		let depth = match self.mode {
+			CameraMode::Reader	=> self.zoom - GLYPH_Z_OFFSET,
+			CameraMode::Orbit	=> self.orbit_state.yaw_pitch_distance.z,
+			_					=> target_translation.map_or(self.zoom, |target| (target - transform.translation).dot(transform.forward())),
+		};
+
+		if depth > 0.0 { depth } else { self.zoom }
+	}
+
+	pub fn is_projection_transitioning(&self) -> bool {
+		self.projection_transition.is_some() || self.projection_transition_requested
+	}
+
+	pub fn is_mode_transitioning(&self) -> bool {
+		self.mode_transition.is_some() || self.mode_transition_requested.is_some()
+	}
+
+	pub fn is_jumping(&self) -> bool {
+		self.jump.is_some()
+	}
+
+	/// Camera sticks to the end of text, false while following is paused
+	pub fn is_tail_following(&self) -> bool {
+		self.tail_follow && self.tail_following
+	}
+
 	pub fn is_moving(&self, transform: &Transform) -> bool {
 		!self.target_translation.abs_diff_eq(transform.translation, 0.001) // TODO: magic numbers bad, settings or constants good
 	}
@@ -1,12 +1,18 @@
 use bevy :: prelude	:: { * };
 
-use lerp :: Lerp;
-
 use super :: TextDescriptor;
+use super :: rows :: RowLayout;
+use super :: easing :: { * };
 use super :: reader_camera :: { * };
 use super :: util :: { * };
 
 use crate :: KeyScroll;
+use crate :: JumpMode;
+use crate :: CameraCommand;
+
+fn clamp_zoom(zoom: f32) -> f32 {
+	zoom.clamp(3.0, 100.0)
+}
 
 pub fn zoom(
 	zoom_scalar_raw	: f32,
@@ -18,23 +24,67 @@ pub fn zoom(
 		zoom_scalar = zoom_scalar_raw * camera.zoom_sensitivity;
 	}
 
-	camera.target_zoom = (zoom_scalar + camera.target_zoom)
-		.min(100.0)
-		.max(3.0);
+	camera.target_zoom = clamp_zoom(zoom_scalar + camera.target_zoom);
 
 	zoom_scalar
 }
 
+/// Zooms by pinch multiplier keeping the point under fingers in place, center is relative to viewport center in world units with y down
+pub fn pinch_zoom(
+	pinch			: f32,
+	center			: Vec2,
+	camera			: &mut ReaderCamera
+) {
+	if !camera.enabled_zoom {
+		return
+	}
+
+	let zoom_old		= camera.target_zoom;
+	camera.target_zoom	= clamp_zoom(zoom_old * pinch);
+
+	// zoom_adjustment keeps viewport center in place so we only shift by the distance between center and fingers
+	let pinch_clamped	= camera.target_zoom / zoom_old;
+	camera.scroll_accum	+= center.y * (1.0 - pinch_clamped);
+	camera.swipe_accum	+= center.x * (1.0 - pinch_clamped);
+}
+
 pub fn apply_zoom(
 	delta_seconds	: f32,
 	camera			: &mut ReaderCamera
  ) {
-	let inertia = (delta_seconds / camera.zoom_easing_seconds).min(1.0);
-	camera.zoom = camera.zoom.lerp(camera.target_zoom, inertia);
+	camera.zoom = camera.easing(camera.zoom_easing).step(camera.zoom, camera.target_zoom, delta_seconds, &mut camera.zoom_easing_state);
+}
+
+/// Projection that shows text plane as it is seen from zoom distance. Perspective only depends on distance,
+/// orthographic is scaled to show the same height perspective with default fov would show on glyphs.
+pub fn projection_for_zoom(
+	projection		: &Projection,
+	zoom			: f32,
+) -> Projection {
+	let Projection::Orthographic(orthographic) = projection else {
+		return projection.clone()
+	};
+
+	let height			= perspective_height(PerspectiveProjection::default().fov, zoom - GLYPH_Z_OFFSET);
+
+	Projection::Orthographic(orthographic_with_height(orthographic, height))
+}
+
+pub fn apply_projection(
+	camera			: &ReaderCamera,
+	projection		: &mut Projection,
+) {
+	let Projection::Orthographic(orthographic) = &*projection else { return };
+
+	let Projection::Orthographic(zoomed) = projection_for_zoom(projection, camera.zoom) else { return };
+	if (zoomed.scale - orthographic.scale).abs() > f32::EPSILON * orthographic.scale {
+		*projection = Projection::Orthographic(zoomed);
+	}
 }
 
 pub fn zoom_adjustment(
 	text_descriptor			: &TextDescriptor,
+	row_layout				: &RowLayout,
 	target_entity_transform	: &Transform,
 	camera_projection		: &Projection,
 	camera_transform		: &mut Transform,
@@ -45,12 +95,21 @@ pub fn zoom_adjustment(
 	// we remove x and y since the amount of visible rows should depend on how much we scrolled and how far the camera is from the surface with text
 	camera_transform_z_only.translation = Vec3::Z * (camera.target_zoom + target_z);
 
-	let frustum				= calc_frustum		(&camera_transform_z_only, camera_projection);
-	let visible_rows_new	= calc_visible_rows	(&frustum, target_z, text_descriptor.glyph_height);
-	let visible_rows_old	= if let Some(rows) = camera.visible_rows_target { rows } else { camera.visible_rows };
+	let projection			= projection_for_zoom(camera_projection, camera.target_zoom);
+	let frustum				= calc_frustum		(&camera_transform_z_only, &projection);
+	let visible_height_new	= calc_visible_height(&frustum, target_z);
+	let visible_height_old	= if let Some(height) = camera.visible_height_target { height } else { camera.visible_height };
 
-	camera.row_offset_delta	= ((visible_rows_old - visible_rows_new) / 2.0) as i32;
-	camera.visible_rows_target = Some(visible_rows_new);
+	// top edge moves by half of the height difference, rows it passes are counted with their own heights.
+	// Scrolling that is still on its way (maybe already sent to app) is kept and the shift goes on top of it
+	let first_row			= camera.row_constant_offset + (camera.row_offset_app as i64 + camera.row_offset_delta as i64) as f32;
+	let top_old				= row_layout.top(first_row);
+	let shift				= (visible_height_old - visible_height_new) / 2.0;
+	let top_new				= top_old + shift;
+
+	camera.row_offset_delta	+= row_layout.rows_in(top_old.min(top_new), shift.abs()).copysign(shift) as i32;
+	camera.visible_rows_target = Some(row_layout.rows_in(top_new, visible_height_new));
+	camera.visible_height_target = Some(visible_height_new);
 
 	// same for visible columns but without sending delta out
 	let visible_columns_new	= calc_visible_columns(&frustum, target_z, text_descriptor.glyph_width);
@@ -63,26 +122,35 @@ pub fn rotation(
 	rows_meta			: &RowsMetaData,
 	camera				: &mut ReaderCamera
 ) -> f32 {
-	let (target_pitch, inertia) = if camera.enabled_rotation && !rows_meta.text_start_reached && !rows_meta.text_end_reached {
+	let (target_pitch, easing) = if camera.reduced_motion {
+		(if camera.pitch_changed { camera.pitch } else { 0.0 }, camera.reduced_motion_easing)
+	} else if camera.enabled_rotation && !rows_meta.text_start_reached && !rows_meta.text_end_reached {
 		let value		= camera.pitch_max;
 
 		let delta_scroll = if camera.invert_y { -delta_scroll_in } else { delta_scroll_in };
 
 		if delta_scroll < 0.0 {
-			(value, delta_seconds / camera.lean_easing_seconds)
+			(value, camera.lean_easing)
 		} else if delta_scroll > 0.0 {
-			(-value, delta_seconds / camera.lean_easing_seconds)
+			(-value, camera.lean_easing)
 		} else if camera.pitch_changed {
-			(camera.pitch, delta_seconds / camera.lean_easing_seconds)
+			(camera.pitch, camera.lean_easing)
 		} else {
-			(0.0, delta_seconds / camera.lean_reset_easing_seconds)
+			(0.0, camera.lean_reset_easing)
 		}
 	} else {
-		(0.0, delta_seconds / camera.lean_reset_easing_seconds)
+		(0.0, camera.lean_reset_easing)
 	};
 
-	camera.pitch		= camera.pitch.lerp(target_pitch, inertia);
-	camera.yaw			= camera.yaw.lerp(0.0, inertia); // we don't have use for yaw in this mode for now
+	// we don't have use for yaw in this mode for now
+	let pitch_yaw		= easing.step(Vec2::new(camera.pitch, camera.yaw), Vec2::new(target_pitch, 0.0), delta_seconds, &mut camera.lean_easing_state);
+
+	camera.pitch		= pitch_yaw.x;
+	camera.yaw			= pitch_yaw.y;
+
+	if camera.reduced_motion {
+		return 0.0
+	}
 
 	let pitch_sin		= camera.pitch.to_radians().sin();
 	let pitch_compensation = pitch_sin * camera.zoom * 2.0;
@@ -92,31 +160,66 @@ pub fn rotation(
 
 pub fn apply_rotation(
 	delta_seconds		: f32,
-	camera				: &ReaderCamera,
+	camera				: &mut ReaderCamera,
 	camera_transform	: &mut Transform,
 ) {
 	let from			= camera_transform.rotation;
 	let to				= Quat::from_axis_angle(Vec3::X, camera.pitch.to_radians());
 
-	let inertia			= (delta_seconds / camera.rotation_easing_seconds).min(1.0);
-	camera_transform.rotation = from.slerp(to, inertia);
+	if camera.is_mode_transitioning() {
+		camera_transform.rotation = to;
+		return
+	}
+
+	camera_transform.rotation = camera.easing(camera.rotation_easing).step(from, to, delta_seconds, &mut camera.rotation_easing_state);
 }
 
 fn translation_swipe(
-	delta_mouse_swipe	: f32,
-	text_descriptor		: &TextDescriptor,
-	camera				: &mut ReaderCamera,
+	swipe_event_occurred	: bool,
+	delta_seconds			: f32,
+	text_descriptor			: &TextDescriptor,
+	camera					: &mut ReaderCamera,
 ) {
-	// we keep column_scroll_accum in range of 0..glyph_width and use the leftover offset to change camera.column
-    while camera.swipe_accum.abs() > text_descriptor.glyph_width {
-		let delta_one		= delta_mouse_swipe.signum();
-		if camera.column > 0 || delta_one.is_sign_positive() {
-			camera.column	= (camera.column as f32 + delta_one) as usize;
-			// clamping
-			camera.column	= camera.column.min(text_descriptor.columns * 2);
+	// slowly snap back to precise column (it is possible to scroll in between columns)
+	if !swipe_event_occurred && camera.scroll_idle_timer.finished() {
+		let target = if camera.swipe_accum.abs() < text_descriptor.glyph_width / 2.0 {
+			0.0
+		} else {
+			text_descriptor.glyph_width * camera.swipe_accum.signum()
+		};
+
+		camera.swipe_accum = ease(camera.swipe_accum, target, delta_seconds, camera.snap_easing_seconds);
+
+		if (target - camera.swipe_accum).abs() < 0.001 {
+			camera.swipe_accum = target;
 		}
+	}
+
+	// we keep swipe_accum in range of 0..glyph_width and use the leftover offset to change camera.column
+	while camera.swipe_accum.abs() >= text_descriptor.glyph_width {
+		let swipe_accum_signum = camera.swipe_accum.signum();
+		camera.column		= (camera.column as i64 + swipe_accum_signum as i64).max(0) as usize;
+
+		camera.swipe_accum -= text_descriptor.glyph_width * swipe_accum_signum;
+	}
+
+	// clamping to text width so that viewport never goes past the edges of text
+	let visible_columns		= if let Some(columns) = camera.visible_columns_target { columns } else { camera.visible_columns };
+	let column_min			= (visible_columns / 2.0).ceil() as i64;
+	let column_max			= (text_descriptor.columns as f32 - visible_columns / 2.0).floor() as i64;
+
+	let (text_left_reached, text_right_reached) = if column_min > column_max {
+		// whole line fits so just look at the center
+		camera.column		= text_descriptor.columns / 2;
+		(true, true)
+	} else {
+		let column			= (camera.column as i64).clamp(column_min, column_max);
+		camera.column		= column as usize;
+		(column == column_min, column == column_max)
+	};
 
-		camera.swipe_accum -= text_descriptor.glyph_width * camera.swipe_accum.signum();
+	if (text_left_reached && camera.swipe_accum < 0.0) || (text_right_reached && camera.swipe_accum > 0.0) {
+		camera.swipe_accum	= 0.0;
 	}
 
 	camera.swipe			= camera.column as f32 * text_descriptor.glyph_width;
@@ -127,49 +230,68 @@ pub struct RowsMetaData {
 	pub row_changed			: bool,
 	pub row_delta			: f32,
 	pub row_max				: f32,
-	pub target_row			: f32,
+	/// row at the top edge of viewport without scrolling in progress
+	pub first_row			: f32,
 	pub visible_rows		: f32,
 	pub visible_rows_half	: f32,
+	pub visible_height_half	: f32,
 	pub text_start_reached	: bool,
 	pub text_end_reached	: bool
 }
 
 fn translation_scroll(
 	scroll_event_occurred	: bool,
+	delta_seconds			: f32,
 	scroll_signum			: f32,
 	pitch_compensation		: f32,
 	rows_meta				: &RowsMetaData,
-	text_descriptor			: &TextDescriptor,
+	row_layout				: &RowLayout,
 	camera					: &mut ReaderCamera,
 ) {
+	// scroll_accum is the distance from the top of the first row, rows above and below it can differ in height
+	let row_height_towards	= |camera: &ReaderCamera| {
+		let first_row		= (rows_meta.first_row + camera.row_offset_delta as f32).floor() as i64;
+		row_layout.height(if camera.scroll_accum < 0.0 { first_row - 1 } else { first_row })
+	};
+
 	// slowly snap back to precise row offset (it is possible to scroll in between rowss)
 	if !scroll_event_occurred && camera.scroll_idle_timer.finished() {
-		let target = if camera.scroll_accum.abs() < text_descriptor.glyph_height / 2.0 {
+		let row_height		= row_height_towards(camera);
+		let target = if camera.scroll_accum.abs() < row_height / 2.0 {
 			0.0
 		} else {
-			text_descriptor.glyph_height * camera.scroll_accum.signum()
+			row_height * camera.scroll_accum.signum()
 		};
 
-		camera.scroll_accum = camera.scroll_accum.lerp(target, 0.1);
+		camera.scroll_accum = ease(camera.scroll_accum, target, delta_seconds, camera.snap_easing_seconds);
 
 		if (target - camera.scroll_accum).abs() < 0.001 {
 			camera.scroll_accum = target;
 		}
 	}
 
-	// we keep row_scroll_accum in range of 0..glyph_height and use the leftover offset to change camera.row_offset_delta
-	while camera.scroll_accum.abs() >= text_descriptor.glyph_height {
+	// we keep row_scroll_accum in range of 0..row height and use the leftover offset to change camera.row_offset_delta
+	loop {
+		let row_height		= row_height_towards(camera);
+		// rows of zero height (folded) are passed without using up any scrolling
+		if camera.scroll_accum.abs() < row_height || camera.scroll_accum == 0.0 {
+			break
+		}
+
 		let scroll_accum_signum = camera.scroll_accum.signum();
 		if (!rows_meta.text_start_reached || scroll_signum.is_sign_positive()) && (!rows_meta.text_end_reached || scroll_signum.is_sign_negative()) {
 			// row_offset_delta tells app that we need to scroll and we expect actual scroll state in row_offset_in
 			camera.row_offset_delta += scroll_accum_signum as i32;
+		} else if row_height <= 0.0 {
+			// can't move past folded row at the edge of text
+			break
 		}
 
 		let row_scroll_unit = scroll_accum_signum;
-		camera.scroll_accum -= text_descriptor.glyph_height * row_scroll_unit;
+		camera.scroll_accum -= row_height * row_scroll_unit;
 	}
 
-	camera.scroll			= (rows_meta.target_row + camera.row_offset_delta as f32) * text_descriptor.glyph_height;
+	camera.scroll			= row_layout.top(rows_meta.first_row + camera.row_offset_delta as f32) + rows_meta.visible_height_half;
 	camera.scroll			+= pitch_compensation;
 
 	if !rows_meta.text_start_reached && !rows_meta.text_end_reached {
@@ -180,6 +302,8 @@ fn translation_scroll(
 pub fn translation(
 	key_scroll_state		: Option<KeyScroll>,
 	delta_wheel				: Option<f32>,
+	delta_wheel_swipe		: Option<f32>,
+	delta_touchpad			: Option<Vec2>,
 	delta_mouse				: Vec2,
 	delta_seconds			: f32,
 
@@ -187,10 +311,15 @@ pub fn translation(
 	rows_meta				: &RowsMetaData,
 
 	text_descriptor			: &TextDescriptor,
+	row_layout				: &RowLayout,
 	target_object_transform	: &Transform,
 	camera					: &mut ReaderCamera,
 ) {
 	let mut scroll_signum = 0.0;
+	let scroll_accum_prev = camera.scroll_accum;
+
+	// speeds are given in rows so they follow the height of the row at the top edge
+	let row_height			= row_layout.height((rows_meta.first_row + camera.row_offset_delta as f32).floor() as i64);
 
 	if camera.enabled_translation {
 		scroll_signum		+= delta_mouse.y;
@@ -198,8 +327,8 @@ pub fn translation(
 		let delta_x			= delta_mouse.x * camera.swipe_sensitivity;
 		let delta_y			= delta_mouse.y * camera.mouse_scroll_sensitivity;
 
-		camera.scroll_accum	+= delta_y * (delta_seconds / camera.mouse_scroll_easing_seconds);
-		camera.swipe_accum	+= delta_x * (delta_seconds / camera.swipe_easing_seconds);
+		camera.scroll_accum	+= delta_y * (MOUSE_REFERENCE_SECONDS / camera.mouse_scroll_easing_seconds);
+		camera.swipe_accum	+= delta_x * (MOUSE_REFERENCE_SECONDS / camera.swipe_easing_seconds);
 	}
 
 	if camera.enabled_scroll && delta_wheel.is_some() {
@@ -209,22 +338,98 @@ pub fn translation(
 		camera.scroll_accum	+= delta_wheel * camera.wheel_scroll_sensitivity;
 	}
 
-	scroll_signum			= scroll_signum.signum();
+	if camera.enabled_scroll {
+		if let Some(key_scroll) = key_scroll_state {
+			let key_scroll_signum = if key_scroll == KeyScroll::Up { -1.0 } else { 1.0 };
+			scroll_signum		+= key_scroll_signum;
+
+			camera.scroll_accum	+= key_scroll_signum * camera.key_scroll_rows_per_second * row_height * delta_seconds;
+		}
+
+		if let Some(delta_wheel_swipe) = delta_wheel_swipe {
+			camera.swipe_accum += delta_wheel_swipe * camera.wheel_swipe_sensitivity;
+		}
+
+		// stick deflection sets scrolling speed, up is towards the start of text
+		let gamepad			= camera.gamepad_input;
+		if gamepad.left.y != 0.0 {
+			scroll_signum		-= gamepad.left.y.signum();
+
+			camera.scroll_accum	-= gamepad.left.y * camera.gamepad.scroll_rows_per_second * row_height * delta_seconds;
+		}
+
+		let gamepad_swipe	= (gamepad.left.x + gamepad.right.x).clamp(-1.0, 1.0);
+		camera.swipe_accum	+= gamepad_swipe * camera.gamepad.swipe_columns_per_second * text_descriptor.glyph_width * delta_seconds;
+
+		let touch			= camera.touch_gesture;
+		if touch.fingers == 2 {
+			camera.swipe_accum	-= touch.drag.x;
+		}
+
+		if let Some(delta_touchpad) = delta_touchpad {
+			camera.swipe_accum	+= delta_touchpad.x;
+		}
+
+		// text follows the finger so we scroll in the opposite direction
+		let gesture_scroll	= if touch.fingers == 1 { Some(-touch.drag.y) } else { delta_touchpad.map(|delta| delta.y) };
+
+		// one finger drag and touchpad are tracked to keep scrolling after they end, any other input stops the momentum
+		let other_scroll	= touch.fingers > 0 || delta_wheel.is_some() || key_scroll_state.is_some() || gamepad.left.y != 0.0;
+
+		if let Some(gesture_scroll) = gesture_scroll {
+			if gesture_scroll != 0.0 {
+				scroll_signum	+= gesture_scroll.signum();
+			}
+
+			camera.scroll_accum	+= gesture_scroll;
+			camera.momentum_state.track(gesture_scroll, delta_seconds);
+		} else if other_scroll || camera.reduced_motion {
+			// reduced motion doesn't coast after the gesture ends
+			camera.momentum_state.stop();
+		} else if touch.released {
+			let momentum	= camera.momentum;
+			camera.momentum_state.release(&momentum, row_height);
+		} else {
+			let momentum	= camera.momentum;
+			camera.momentum_state.pause(&momentum, row_height, delta_seconds);
+		}
+
+		let velocity		= camera.momentum_state.velocity() * input_direction(text_descriptor);
+		if (rows_meta.text_start_reached && velocity < 0.0) || (rows_meta.text_end_reached && velocity > 0.0) {
+			camera.momentum_state.stop();
+		}
+
+		let momentum		= camera.momentum;
+		let momentum_scroll	= camera.momentum_state.advance(&momentum, row_height, delta_seconds);
+		if momentum_scroll != 0.0 {
+			scroll_signum		+= momentum_scroll.signum();
+
+			camera.scroll_accum	+= momentum_scroll;
+		}
+	}
+
+	// input goes up and down the screen, bottom anchored rows go the other way
+	let input_direction		= input_direction(text_descriptor);
+	camera.scroll_accum		= scroll_accum_prev + (camera.scroll_accum - scroll_accum_prev) * input_direction;
+	scroll_signum			= scroll_signum.signum() * input_direction;
 
 	// Column related stuff
 	translation_swipe(
-		delta_mouse.x,
+		delta_wheel_swipe.is_some() || delta_touchpad.is_some() || camera.gamepad_input.left.x != 0.0 || camera.gamepad_input.right.x != 0.0 || camera.touch_gesture.fingers == 2,
+		delta_seconds,
 		text_descriptor,
 		camera
 	);
 
 	// Row related stuff
 	translation_scroll(
-		delta_wheel.is_some(),
+		delta_wheel.is_some() || key_scroll_state.is_some() || camera.gamepad_input.left.y != 0.0 || camera.touch_gesture.fingers > 0 || camera.momentum_state.is_active(),
+		delta_seconds,
 		scroll_signum,
-		pitch_compensation,
+		// lean is the same on screen, so compensation goes against bottom anchored rows
+		pitch_compensation * input_direction,
 		rows_meta,
-		text_descriptor,
+		row_layout,
 		camera,
 	);
 
@@ -233,7 +438,7 @@ pub fn translation(
 	let yaw_radians			= camera.yaw.to_radians();
 	let pitch_radians		= camera.pitch.to_radians();
 
-	let scroll				= if camera.invert_y { camera.scroll } else { -camera.scroll };
+	let scroll				= scroll_to_y(camera.scroll, text_descriptor, camera);
 
 	camera.target_translation = target_object_transform.translation
 		+ camera.zoom * unit_vector_from_yaw_and_pitch(yaw_radians, pitch_radians)
@@ -241,17 +446,335 @@ pub fn translation(
 		+ Vec3::Y * scroll
 	;
 
-	let easing_target = if key_scroll_state.is_some() { camera.translation_easing_scroll_seconds } else { camera.translation_easing_seconds };
-	camera.translation_easing_current = camera.translation_easing_current.lerp(easing_target, 0.1);
+	let blend_target		= if key_scroll_state.is_some() { 1.0 } else { 0.0 };
+	camera.translation_scroll_blend = ease(camera.translation_scroll_blend, blend_target, delta_seconds, camera.translation_easing_blend_seconds);
 }
 
 pub fn apply_translation(
 	delta_seconds			: f32,
 	rows_meta				: &RowsMetaData,
-	camera					: &ReaderCamera,
+	camera					: &mut ReaderCamera,
+	camera_transform		: &mut Transform,
+) {
+	// mode transition blends towards wherever reader wants to be, so no easing of our own
+	if camera.is_mode_transitioning() {
+		camera.jump				= None;
+		camera.translation_easing_state.reset();
+		camera_transform.translation = camera.target_translation;
+		return
+	}
+
+	let jump_rows			= rows_meta.visible_rows + rows_meta.visible_rows_half;
+
+	if camera.jump_mode == JumpMode::Instant || camera.reduced_motion {
+		let instant_translate	= rows_meta.row_delta.abs() > jump_rows;
+		if instant_translate {
+			camera.translation_easing_state.reset();
+			camera_transform.translation = camera.target_translation;
+		} else {
+			ease_translation(delta_seconds, camera, camera_transform);
+		}
+		return
+	}
+
+	// distance to target instead of row_delta because scroll commands move the target before app catches up with row offset
+	let distance			= (camera.target_translation - camera_transform.translation).truncate().length();
+	let screen_height		= rows_meta.visible_height_half * 2.0;
+
+	// same one and a half screens as jump_rows but in world units, rows can differ in height
+	if camera.jump.is_none() && distance > screen_height + rows_meta.visible_height_half {
+		// longer jumps take more time but never more than jump_max_seconds
+		let screens			= distance / screen_height.max(f32::EPSILON);
+
+		camera.translation_easing_state.reset();
+
+		camera.jump = Some(Jump {
+			from			: camera_transform.translation,
+			elapsed			: 0.0,
+			duration		: camera.jump_max_seconds * (screens.log10() / 2.0).clamp(0.5, 1.0),
+			zoom_out		: camera.zoom * camera.jump_zoom_out,
+		});
+	}
+
+	if let Some(jump) = camera.jump.as_mut() {
+		jump.elapsed		+= delta_seconds;
+
+		let t				= (jump.elapsed / jump.duration).min(1.0);
+		let travel			= t * t * (3.0 - 2.0 * t);
+		let zoom_out		= jump.zoom_out * 4.0 * t * (1.0 - t);
+
+		camera_transform.translation = jump.from.lerp(camera.target_translation, travel) + Vec3::Z * zoom_out;
+
+		if t >= 1.0 {
+			camera.jump		= None;
+		}
+
+		return
+	}
+
+	ease_translation(delta_seconds, camera, camera_transform);
+}
+
+/// Screen input moves towards higher rows when positive, bottom anchored text flips it
+pub fn input_direction(text_descriptor: &TextDescriptor) -> f32 {
+	-text_descriptor.row_direction()
+}
+
+/// Rows to scroll by for one line down the screen, lines go up and down the screen while ScrollBy goes along rows
+pub fn rows_down(text_descriptor: Option<&TextDescriptor>) -> i32 {
+	text_descriptor.map_or(1, |text_descriptor| input_direction(text_descriptor) as i32)
+}
+
+// distance from the text origin along rows to camera y, invert_y flips it once more
+fn scroll_to_y(
+	scroll			: f32,
+	text_descriptor	: &TextDescriptor,
+	camera			: &ReaderCamera,
+) -> f32 {
+	let y = scroll * text_descriptor.row_direction();
+	if camera.invert_y { -y } else { y }
+}
+
+/// Puts the row that was at the top edge of viewport before RowsEdited back there without any easing
+pub fn apply_row_anchor(
+	visible_height_half		: f32,
+	text_descriptor			: &TextDescriptor,
+	row_layout				: &RowLayout,
+	camera					: &mut ReaderCamera,
+	camera_transform		: &mut Transform,
+) {
+	let Some((row, in_row)) = camera.row_anchor_pending.take() else { return };
+
+	// wrapped row may have become shorter
+	let visual				= camera.row_map.to_visual(row) as f32 + in_row.min(camera.row_map.visual_rows(row) as f32);
+	let scroll				= row_layout.top(camera.row_constant_offset + visual) + visible_height_half;
+	let scroll				= scroll_to_y(scroll, text_descriptor, camera);
+
+	// target and jump move along so easing goes on from where it was
+	let delta				= scroll - camera_transform.translation.y;
+	camera_transform.translation.y += delta;
+	camera.target_translation.y += delta;
+	if let Some(jump) = camera.jump.as_mut() {
+		jump.from.y			+= delta;
+	}
+}
+
+fn ease_translation(
+	delta_seconds			: f32,
+	camera					: &mut ReaderCamera,
 	camera_transform		: &mut Transform,
 ) {
-	let instant_translate	= rows_meta.row_delta.abs() > (rows_meta.visible_rows + rows_meta.visible_rows_half);
-	let inertia				= if !instant_translate { (delta_seconds / camera.translation_easing_current).min(1.0) } else { 1.0 };
-	camera_transform.translation = camera_transform.translation.lerp(camera.target_translation, inertia);
-}
\ No newline at end of file
+	let easing				= camera.easing(camera.translation_easing.blend(&camera.translation_scroll_easing, camera.translation_scroll_blend));
+	camera_transform.translation = easing.step(camera_transform.translation, camera.target_translation, delta_seconds, &mut camera.translation_easing_state);
+}
+
+/// Commands that don't need text to be applied, returns false for the ones that do
+pub fn command_generic(
+	command		: &CameraCommand,
+	camera		: &mut ReaderCamera,
+) -> bool {
+	match *command {
+		CameraCommand::SetZoom(zoom) => camera.target_zoom = clamp_zoom(zoom),
+		CameraCommand::ZoomBy(delta) => { zoom(delta, camera); },
+		CameraCommand::SetColumn(column) => camera.column = column,
+		CameraCommand::SetMode(mode) => camera.set_mode(mode),
+		CameraCommand::SetPerspective(perspective) => camera.set_perspective(perspective),
+		_ => return false,
+	}
+
+	true
+}
+
+/// Amount of visual rows text takes, see RowMap
+pub fn visual_row_count(
+	text_descriptor	: &TextDescriptor,
+	camera			: &ReaderCamera,
+) -> u32 {
+	camera.row_map.to_visual(text_descriptor.rows as u32)
+}
+
+fn row_offset_max(
+	text_descriptor	: &TextDescriptor,
+	row_layout		: &RowLayout,
+	camera			: &ReaderCamera,
+) -> i64 {
+	let visible_height_half	= if let Some(height) = camera.visible_height_target { height } else { camera.visible_height } / 2.0;
+
+	// same condition as text_end_reached in reader_mode system: last row in the middle of viewport
+	let last_row_top		= row_layout.top(visual_row_count(text_descriptor, camera) as f32 - 1.0);
+	(row_layout.row_at(last_row_top - visible_height_half) - camera.row_constant_offset).max(0.0) as i64
+}
+
+fn scroll_to_row(
+	row_offset		: i64,
+	row_offset_max	: i64,
+	camera			: &mut ReaderCamera,
+) {
+	let row_offset			= row_offset.clamp(0, row_offset_max);
+
+	camera.row_offset_delta	= (row_offset - camera.row_offset_app as i64) as i32;
+	// land exactly on the row instead of keeping leftovers from wheel scrolling
+	camera.scroll_accum		= 0.0;
+}
+
+/// Keeps viewport at the end of text while it grows if it was there already, see ReaderCamera::tail_follow
+pub fn tail_follow(
+	text_descriptor	: &TextDescriptor,
+	row_layout		: &RowLayout,
+	camera			: &mut ReaderCamera,
+) {
+	let rows				= visual_row_count(text_descriptor, camera);
+	let grown				= rows > camera.tail_rows_prev;
+	camera.tail_rows_prev	= rows;
+
+	if !camera.tail_follow {
+		camera.tail_following = false;
+		return
+	}
+
+	let row_offset_max		= row_offset_max(text_descriptor, row_layout, camera);
+	let row_offset			= camera.row_offset_app as i64 + camera.row_offset_delta as i64;
+
+	// new rows are scrolled to like any other rows, anything else that moved camera away from the end pauses following
+	if camera.tail_following && grown && row_offset < row_offset_max {
+		scroll_to_row(row_offset_max, row_offset_max, camera);
+	} else {
+		camera.tail_following = row_offset >= row_offset_max;
+	}
+}
+
+/// Row commands, all of them end up in row_offset_delta so the motion looks the same as regular scrolling.
+/// Rows in commands are logical, relative ones scroll through visual rows
+pub fn command_rows(
+	command			: &CameraCommand,
+	text_descriptor	: &TextDescriptor,
+	row_layout		: &RowLayout,
+	camera			: &mut ReaderCamera,
+) {
+	let visible_rows		= if let Some(rows) = camera.visible_rows_target { rows } else { camera.visible_rows };
+	let visible_height_half	= if let Some(height) = camera.visible_height_target { height } else { camera.visible_height } / 2.0;
+
+	let row_offset_max		= row_offset_max(text_descriptor, row_layout, camera);
+	let visual				= |row: u32| camera.row_map.to_visual(row) as i64;
+
+	// rows between the top edge and the middle of viewport when given row is in the middle
+	let rows_above			= |row: i64| row_layout.rows_in(row_layout.top(row as f32) - visible_height_half, visible_height_half) as i64;
+	let row_offset_current	= camera.row_offset_app as i64 + camera.row_offset_delta as i64;
+
+	let fully_visible		= (visible_rows.floor() as i64).max(1);
+	let page				= (fully_visible - camera.page_overlap_rows as i64).max(1);
+	let half_page			= (fully_visible / 2).max(1);
+	// pages go up and down the screen, bottom anchored rows go the other way
+	let rows_down			= input_direction(text_descriptor) as i64;
+
+	let row_offset_new = match *command {
+		CameraCommand::ScrollTo(row) => visual(row),
+		CameraCommand::ScrollBy(rows) => row_offset_current + rows as i64,
+		CameraCommand::PageUp => row_offset_current - page * rows_down,
+		CameraCommand::PageDown => row_offset_current + page * rows_down,
+		CameraCommand::HalfPageUp => row_offset_current - half_page * rows_down,
+		CameraCommand::HalfPageDown => row_offset_current + half_page * rows_down,
+		CameraCommand::DocumentStart => 0,
+		CameraCommand::DocumentEnd => row_offset_max,
+		CameraCommand::CenterOnRow(row) => visual(row) - rows_above(visual(row)),
+		CameraCommand::RevealRange { start, end } => {
+			// every visual row of wrapped end row
+			let (start, end)	= (visual(start), (visual(end.saturating_add(1)) - 1).max(visual(start)));
+			if start < row_offset_current {
+				start
+			} else if end >= row_offset_current + fully_visible {
+				(end + 1 - fully_visible).min(start)
+			} else {
+				row_offset_current
+			}
+		},
+		_ => return,
+	};
+
+	scroll_to_row(row_offset_new, row_offset_max, camera);
+}
+
+// margins that don't fit into viewport keep the caret in the middle
+fn scrolloff_fit(before: i64, after: i64, fully_visible: i64) -> (i64, i64) {
+	if before + after >= fully_visible {
+		let half = (fully_visible - 1) / 2;
+		(half, fully_visible - 1 - half)
+	} else {
+		(before, after)
+	}
+}
+
+/// Scrolls to keep caret within scrolloff margins, only reacts when caret moves so user can still scroll away from it
+pub fn caret_follow(
+	text_descriptor	: &TextDescriptor,
+	row_layout		: &RowLayout,
+	camera			: &mut ReaderCamera,
+) {
+	let Some((caret_row, caret_column)) = camera.caret else { return };
+
+	if !camera.caret_follow || camera.caret_followed == camera.caret {
+		return
+	}
+
+	camera.caret_followed	= camera.caret;
+
+	// rows
+
+	let visible_rows		= if let Some(rows) = camera.visible_rows_target { rows } else { camera.visible_rows };
+	let fully_visible		= (visible_rows.floor() as i64).max(1);
+
+	let row_offset			= camera.row_offset_app as i64 + camera.row_offset_delta as i64;
+	let caret_row			= camera.row_map.to_visual(caret_row) as i64;
+
+	// rows grow towards the top edge of bottom anchored text
+	let (scrolloff_top, scrolloff_bottom) = if text_descriptor.bottom_anchored {
+		(camera.scrolloff_bottom, camera.scrolloff_top)
+	} else {
+		(camera.scrolloff_top, camera.scrolloff_bottom)
+	};
+
+	let (top, bottom)		= scrolloff_fit(scrolloff_top as i64, scrolloff_bottom as i64, fully_visible);
+
+	let row_offset_new		= if caret_row < row_offset + top {
+		Some(caret_row - top)
+	} else if caret_row > row_offset + fully_visible - 1 - bottom {
+		Some(caret_row + bottom + 1 - fully_visible)
+	} else {
+		None
+	};
+
+	if let Some(row_offset_new) = row_offset_new {
+		let row_offset_max	= row_offset_max(text_descriptor, row_layout, camera);
+		scroll_to_row(row_offset_new, row_offset_max, camera);
+	}
+
+	// columns
+
+	let visible_columns		= if let Some(columns) = camera.visible_columns_target { columns } else { camera.visible_columns };
+
+	// nothing to scroll horizontally if the whole line fits
+	if text_descriptor.columns as f32 <= visible_columns {
+		return
+	}
+
+	let visible_columns_half = (visible_columns / 2.0).floor() as i64;
+	let fully_visible		= (visible_columns.floor() as i64).max(1);
+
+	let column_left			= camera.column as i64 - visible_columns_half;
+	let caret_column		= caret_column as i64;
+
+	let (left, right)		= scrolloff_fit(camera.scrolloff_left as i64, camera.scrolloff_right as i64, fully_visible);
+
+	let column_left_new		= if caret_column < column_left + left {
+		Some(caret_column - left)
+	} else if caret_column > column_left + fully_visible - 1 - right {
+		Some(caret_column + right + 1 - fully_visible)
+	} else {
+		None
+	};
+
+	if let Some(column_left_new) = column_left_new {
+		let column = (column_left_new + visible_columns_half).clamp(visible_columns_half, text_descriptor.columns as i64 - visible_columns_half);
+		command_generic(&CameraCommand::SetColumn(column as usize), camera);
+	}
+}
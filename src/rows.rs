@@ -0,0 +1,249 @@
+use std :: ops :: Range;
+
+use bevy :: prelude :: *;
+
+use super :: TextDescriptor;
+
+// Rows go down from the origin of target entity, row 0 starts at the top. Without RowHeights every row is
+// glyph_height tall, with it the rows it covers take their own height and the rest are glyph_height tall again.
+
+/// Heights of rows that differ from glyph_height (headings, inline images, code blocks), put it next to TextDescriptor.
+/// Kept as prefix sums so position of any row is a lookup and row at any position is a binary search.
+#[derive(Component, Clone, Debug, Default)]
+pub struct RowHeights {
+	// distance from the start of text to the top of every row plus the bottom of the last one
+	tops	: Vec<f32>,
+}
+
+impl RowHeights {
+	pub fn new(heights: impl IntoIterator<Item = f32>) -> Self {
+		let mut row_heights = Self { tops: vec![0.0] };
+		for height in heights {
+			row_heights.push(height);
+		}
+
+		row_heights
+	}
+
+	/// Adds a row after the last one
+	pub fn push(&mut self, height: f32) {
+		let bottom = self.tops.last().copied().unwrap_or(0.0);
+		if self.tops.is_empty() {
+			self.tops.push(0.0);
+		}
+
+		self.tops.push(bottom + height.max(0.0));
+	}
+
+	/// Changes height of given row, every row below it moves. Panics if row is out of the table
+	pub fn set(&mut self, row: usize, height: f32) {
+		let delta = height.max(0.0) - self.height(row).expect("row is out of RowHeights");
+		for top in self.tops[row + 1..].iter_mut() {
+			*top += delta;
+		}
+	}
+
+	/// Amount of rows in the table
+	pub fn len(&self) -> usize {
+		self.tops.len().saturating_sub(1)
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	pub fn height(&self, row: usize) -> Option<f32> {
+		Some(self.tops.get(row + 1)? - self.tops[row])
+	}
+
+	/// Distance from the start of text to the top of given row
+	pub fn top(&self, row: usize) -> Option<f32> {
+		self.tops.get(row).copied()
+	}
+}
+
+/// Row geometry of a text, TextDescriptor with optional RowHeights
+#[derive(Clone, Copy, Debug)]
+pub struct RowLayout<'a> {
+	glyph_height	: f32,
+	heights			: Option<&'a RowHeights>,
+}
+
+impl<'a> RowLayout<'a> {
+	pub fn new(text_descriptor: &TextDescriptor, heights: Option<&'a RowHeights>) -> Self {
+		Self { glyph_height: text_descriptor.glyph_height, heights: heights.filter(|heights| !heights.is_empty()) }
+	}
+
+	/// Height of given row, rows outside of RowHeights are glyph_height tall
+	pub fn height(&self, row: i64) -> f32 {
+		match self.heights {
+			Some(heights) if row >= 0 => heights.height(row as usize).unwrap_or(self.glyph_height),
+			_ => self.glyph_height,
+		}
+	}
+
+	/// Distance from the start of text to given row, fractions go through the height of the row
+	pub fn top(&self, row: f32) -> f32 {
+		let Some(heights) = self.heights else {
+			return row * self.glyph_height
+		};
+
+		let rows			= heights.len();
+		if row < 0.0 {
+			return row * self.glyph_height
+		}
+
+		let bottom			= heights.tops[rows];
+		if row >= rows as f32 {
+			return bottom + (row - rows as f32) * self.glyph_height
+		}
+
+		let index			= row.floor() as usize;
+		heights.tops[index] + (row - index as f32) * self.height(index as i64)
+	}
+
+	/// Row at given distance from the start of text, inverse of top
+	pub fn row_at(&self, y: f32) -> f32 {
+		let Some(heights) = self.heights else {
+			return y / self.glyph_height
+		};
+
+		let rows			= heights.len();
+		if y < 0.0 {
+			return y / self.glyph_height
+		}
+
+		let bottom			= heights.tops[rows];
+		if y >= bottom {
+			return rows as f32 + (y - bottom) / self.glyph_height
+		}
+
+		// last row that starts at or above y, rows of zero height are skipped over
+		let index			= heights.tops[..rows].partition_point(|top| *top <= y) - 1;
+		let height			= self.height(index as i64);
+		let fraction		= if height > 0.0 { (y - heights.tops[index]) / height } else { 0.0 };
+
+		index as f32 + fraction
+	}
+
+	/// Amount of rows (with fractions) in given span of height that starts at given distance from the start of text
+	pub fn rows_in(&self, top: f32, height: f32) -> f32 {
+		if self.heights.is_none() {
+			return height / self.glyph_height
+		}
+
+		self.row_at(top + height) - self.row_at(top)
+	}
+}
+
+/// Logical rows of the app (lines of a document) to visual rows the camera scrolls through: folded rows take no
+/// visual rows, soft-wrapped rows take more than one. Put it next to TextDescriptor, rows past the table are one to one.
+/// TextDescriptor::rows, row offsets, caret and row commands are logical, RowHeights and picking are visual.
+#[derive(Component, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RowMap {
+	// first visual row of every logical row plus the end of the last one
+	starts	: Vec<u32>,
+}
+
+impl RowMap {
+	/// Takes amount of visual rows for every logical row, 0 hides the row
+	pub fn new(visual_rows: impl IntoIterator<Item = u32>) -> Self {
+		let mut row_map = Self::default();
+		for count in visual_rows {
+			row_map.push(count);
+		}
+
+		row_map
+	}
+
+	/// Adds a logical row after the last one
+	pub fn push(&mut self, visual_rows: u32) {
+		if self.starts.is_empty() {
+			self.starts.push(0);
+		}
+
+		let end = self.starts[self.starts.len() - 1];
+		self.starts.push(end + visual_rows);
+	}
+
+	/// Changes amount of visual rows of given logical row. Panics if row is out of the table
+	pub fn set(&mut self, row: u32, visual_rows: u32) {
+		let row			= row as usize;
+		let count		= self.starts[row + 1] - self.starts[row];
+		for start in self.starts[row + 1..].iter_mut() {
+			*start		= *start - count + visual_rows;
+		}
+	}
+
+	/// Replaces logical rows in range with new ones taking given amounts of visual rows, like RowsEdited does.
+	/// Empty map stays identity, rows of the range that are out of the table are ignored
+	pub fn replace(&mut self, rows: Range<u32>, visual_rows: impl IntoIterator<Item = u32>) {
+		let len			= self.len();
+		if self.is_empty() || rows.start as usize > len {
+			return
+		}
+
+		let start		= rows.start as usize;
+		let end			= (rows.end as usize).clamp(start, len);
+
+		let mut counts	: Vec<u32> = self.starts.windows(2).map(|pair| pair[1] - pair[0]).collect();
+		counts.splice(start..end, visual_rows);
+
+		*self			= Self::new(counts);
+	}
+
+	/// Folds every row in range, rows of the range that are out of the table are ignored
+	pub fn hide(&mut self, rows: Range<u32>) {
+		for row in rows.start..rows.end.min(self.len() as u32) {
+			self.set(row, 0);
+		}
+	}
+
+	/// Amount of logical rows in the table
+	pub fn len(&self) -> usize {
+		self.starts.len().saturating_sub(1)
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	pub fn is_hidden(&self, row: u32) -> bool {
+		self.visual_rows(row) == 0
+	}
+
+	/// Amount of visual rows given logical row takes
+	pub fn visual_rows(&self, row: u32) -> u32 {
+		let row = row as usize;
+		if row < self.len() { self.starts[row + 1] - self.starts[row] } else { 1 }
+	}
+
+	/// First visual row of given logical row, hidden rows give the first visual row after them
+	pub fn to_visual(&self, row: u32) -> u32 {
+		let rows = self.len();
+		if rows == 0 {
+			return row
+		}
+
+		match self.starts.get(row as usize) {
+			Some(start) => *start,
+			None => self.starts[rows] + (row - rows as u32),
+		}
+	}
+
+	/// Logical row given visual row belongs to
+	pub fn to_logical(&self, row: u32) -> u32 {
+		let rows = self.len();
+		if rows == 0 {
+			return row
+		}
+
+		let end = self.starts[rows];
+		if row >= end {
+			return rows as u32 + (row - end)
+		}
+
+		// the last logical row starting at or before it, hidden rows start at the same place as the next one
+		(self.starts[..rows].partition_point(|start| *start <= row) - 1) as u32
+	}
+}
@@ -1,88 +1,208 @@
 use bevy :: {
 	prelude	:: { * },
 	input	:: mouse :: { MouseMotion, MouseScrollUnit, MouseWheel },
-	render	:: camera :: { * },
+	render	:: { camera :: { * }, primitives :: Aabb },
+	window	:: { CursorGrabMode, PrimaryWindow },
 };
 
 use super :: CameraMode;
 use super :: TextDescriptor;
+use super :: rows :: { RowHeights, RowLayout, RowMap };
+use super :: actions :: CameraAction;
+use super :: gamepad :: { GamepadInput, read_gamepad };
+use super :: touch :: touch_gesture;
+use super :: events :: { * };
 use super :: reader_camera :: { * };
 use super :: util :: { * };
+use super :: orbit :: entity_bounds;
 
 use crate :: KeyScroll;
 
+pub fn camera_actions(
+		key_code				: Res<Input<KeyCode>>,
+		mouse_button			: Res<Input<MouseButton>>,
+	mut camera_command_writer	: EventWriter<ReaderCameraCommand>,
+	mut q_camera				: Query<(Entity, &mut ReaderCamera)>,
+		q_text_descriptor		: Query<&TextDescriptor>,
+) {
+	for (camera_entity, mut camera) in q_camera.iter_mut() {
+		if !camera.input_focus {
+			continue;
+		}
+
+		let actions_just_pressed: Vec<CameraAction> = CameraAction::ALL.into_iter()
+			.filter(|action| camera.actions.just_pressed(*action, &key_code, &mouse_button))
+			.collect();
+		let just_pressed = |action| actions_just_pressed.contains(&action);
+
+		if just_pressed(CameraAction::TogglePerspective) {
+			let perspective = !camera.perspective;
+			camera.set_perspective(perspective);
+		}
+
+		if just_pressed(CameraAction::SwitchMode) {
+			// Follow and Reader make no sense without target
+			let mode = match (camera.mode, camera.target_entity.is_some()) {
+				(CameraMode::Fly, _)		=> CameraMode::Orbit,
+				(CameraMode::Orbit, true)	=> CameraMode::Follow,
+				(CameraMode::Follow, _)		=> CameraMode::Reader,
+				_							=> CameraMode::Fly,
+			};
+
+			camera.set_mode(mode);
+		}
+
+		let mut commands = Vec::new();
+
+		match camera.mode {
+			CameraMode::Reader => {
+				if camera.enabled_scroll {
+					let rows_down = reader::rows_down(camera.target_entity.and_then(|entity| q_text_descriptor.get(entity).ok()));
+
+					if just_pressed(CameraAction::ScrollLineUp)		{ commands.push(CameraCommand::ScrollBy(-rows_down)) }
+					if just_pressed(CameraAction::ScrollLineDown)	{ commands.push(CameraCommand::ScrollBy(rows_down)) }
+					if just_pressed(CameraAction::PageUp)			{ commands.push(CameraCommand::PageUp) }
+					if just_pressed(CameraAction::PageDown)			{ commands.push(CameraCommand::PageDown) }
+					if just_pressed(CameraAction::HalfPageUp)		{ commands.push(CameraCommand::HalfPageUp) }
+					if just_pressed(CameraAction::HalfPageDown)		{ commands.push(CameraCommand::HalfPageDown) }
+					if just_pressed(CameraAction::DocumentStart)	{ commands.push(CameraCommand::DocumentStart) }
+					if just_pressed(CameraAction::DocumentEnd)		{ commands.push(CameraCommand::DocumentEnd) }
+				}
+
+				if camera.enabled_zoom {
+					if just_pressed(CameraAction::ZoomIn)			{ commands.push(CameraCommand::ZoomBy(-1.0)) }
+					if just_pressed(CameraAction::ZoomOut)			{ commands.push(CameraCommand::ZoomBy(1.0)) }
+					if just_pressed(CameraAction::ZoomReset)		{ commands.push(CameraCommand::SetZoom(camera.zoom_default)) }
+				}
+			},
+			CameraMode::Follow => {
+				if camera.enabled_zoom {
+					let zoom = if just_pressed(CameraAction::ZoomIn) {
+						camera.zoom * (1.0 - 0.1 * camera.zoom_sensitivity)
+					} else if just_pressed(CameraAction::ZoomOut) {
+						camera.zoom * (1.0 + 0.1 * camera.zoom_sensitivity)
+					} else if just_pressed(CameraAction::ZoomReset) {
+						camera.zoom_default
+					} else {
+						camera.zoom
+					};
+
+					camera.zoom = zoom.clamp(1.0, 100.0);
+				}
+			},
+			CameraMode::Orbit => {
+				if camera.enabled_zoom {
+					let control = camera.orbit;
+					let target_distance = camera.orbit_state.target_yaw_pitch_distance.z;
+					let scale = if just_pressed(CameraAction::ZoomIn) {
+						1.0 - control.zoom_sensitivity
+					} else if just_pressed(CameraAction::ZoomOut) {
+						1.0 + control.zoom_sensitivity
+					} else if just_pressed(CameraAction::ZoomReset) {
+						camera.zoom_default / target_distance
+					} else {
+						1.0
+					};
+
+					camera.orbit_state.zoom(scale, &control);
+				}
+			},
+			CameraMode::Fly => (),
+		}
+
+		camera_command_writer.send_batch(commands.into_iter().map(|command| ReaderCameraCommand { camera: camera_entity, command }));
+	}
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn gamepad_input(
+		gamepads				: Res<Gamepads>,
+		gamepad_axes			: Res<Axis<GamepadAxis>>,
+		gamepad_buttons			: Res<Input<GamepadButton>>,
+		gamepad_button_axes		: Res<Axis<GamepadButton>>,
+	mut camera_command_writer	: EventWriter<ReaderCameraCommand>,
+	mut q_camera				: Query<(Entity, &mut ReaderCamera)>,
+		q_text_descriptor		: Query<&TextDescriptor>,
+) {
+	for (camera_entity, mut camera) in q_camera.iter_mut() {
+		let input = if camera.input_focus {
+			read_gamepad(&camera.gamepad, &gamepads, &gamepad_axes, &gamepad_buttons, &gamepad_button_axes)
+		} else {
+			GamepadInput::default()
+		};
+
+		camera.gamepad_input = input;
+
+		if camera.mode != CameraMode::Reader || !camera.enabled_scroll {
+			continue;
+		}
+
+		let mut commands = Vec::new();
+
+		let rows_down = reader::rows_down(camera.target_entity.and_then(|entity| q_text_descriptor.get(entity).ok()));
+
+		if input.dpad_up	{ commands.push(CameraCommand::ScrollBy(-rows_down)) }
+		if input.dpad_down	{ commands.push(CameraCommand::ScrollBy(rows_down)) }
+		if input.dpad_left	{ commands.push(CameraCommand::PageUp) }
+		if input.dpad_right	{ commands.push(CameraCommand::PageDown) }
+
+		camera_command_writer.send_batch(commands.into_iter().map(|command| ReaderCameraCommand { camera: camera_entity, command }));
+	}
+}
+
 pub fn fly_mode_keyboard(
-		time		: Res<Time>,
-		key_code	: Res<Input<KeyCode>>,
-	mut q_camera	: Query<(&mut ReaderCamera, &mut Transform, &mut Projection)>,
+		time			: Res<Time>,
+		key_code		: Res<Input<KeyCode>>,
+		mouse_button	: Res<Input<MouseButton>>,
+	mut q_camera		: Query<(&mut ReaderCamera, &mut Transform)>,
 ) {
 	let delta_seconds = time.delta_seconds();
 
-	for (mut camera, mut camera_transform, mut projection) in q_camera.iter_mut() {
-		if camera.mode != CameraMode::Fly || !camera.enabled_translation {
+	for (mut camera, mut camera_transform) in q_camera.iter_mut() {
+		if camera.mode != CameraMode::Fly || !camera.enabled_translation || !camera.input_focus {
 			continue;
 		}
 
+		let actions = &camera.actions;
+		let gamepad = camera.gamepad_input;
 		let (axis_h, axis_v, axis_float) = (
-			movement_axis(&key_code, camera.key_right,		camera.key_left),
-			movement_axis(&key_code, camera.key_backward,	camera.key_forward),
-			movement_axis(&key_code, camera.key_up,			camera.key_down),
+			actions.axis(CameraAction::MoveRight,		CameraAction::MoveLeft,		&key_code, &mouse_button) + gamepad.left.x,
+			actions.axis(CameraAction::MoveBackward,	CameraAction::MoveForward,	&key_code, &mouse_button) - gamepad.left.y,
+			actions.axis(CameraAction::MoveUp,			CameraAction::MoveDown,		&key_code, &mouse_button) + gamepad.triggers,
 		);
 
-		let modper = camera.mod_perspective;
-		let perspective_mod = (modper.is_some() && key_code.pressed(modper.unwrap())) || modper.is_none();
-		if perspective_mod && key_code.just_pressed(camera.key_perspective) {
-			let toggle 	= !camera.perspective;
-			camera.perspective = toggle;
-
-			*projection =
-			if camera.perspective {
-				Projection::Perspective(PerspectiveProjection::default())
-			} else {
-				camera.yaw = 0.0;
-				camera.pitch = 0.0;
-
-				Projection::Orthographic(
-					OrthographicProjection {
-						scale: 3.0,
-						scaling_mode: ScalingMode::FixedVertical(2.0),
-						..default()
-					}
-				)
-			}
-		}
+		let sprint = if actions.pressed(CameraAction::Sprint, &key_code, &mouse_button) { camera.sprint_multiplier } else { 1.0 };
+		let scale = camera.speed_scale * sprint;
 
 		let rotation = camera_transform.rotation;
-		let accel: Vec3 = (strafe_vector(&rotation) * axis_h)
+		let direction: Vec3 = (strafe_vector(&rotation) * axis_h)
 			+ (forward_walk_vector(&rotation) * axis_v)
 			+ (Vec3::Y * axis_float);
-		let accel: Vec3 = if accel.length() != 0.0 {
-			accel.normalize() * camera.accel
+		// not normalized to keep analog stick deflection
+		let accel: Vec3 = direction.clamp_length_max(1.0) * camera.accel * scale;
+
+		// exact solution of dv/dt = accel - drag * v for constant accel so that flying doesn't depend on frame rate
+		let velocity = if camera.drag > 0.0 {
+			let decay = (-camera.drag * delta_seconds).exp();
+			camera.velocity * decay + accel * (1.0 - decay) / camera.drag
 		} else {
-			Vec3::ZERO
+			camera.velocity + accel * delta_seconds
 		};
 
-		camera.velocity = accel * delta_seconds * 2.5;
-
-		// clamp within max speed
-		// if camera.velocity.length() > camera.max_speed {
-		// 	camera.velocity = camera.velocity.normalize() * camera.max_speed;
-		// }
+		camera.velocity = velocity.clamp_length_max(camera.max_speed * scale);
 
-		camera.target_translation = camera.target_translation + camera.velocity;
-
-		let from			= camera_transform.translation;
-		let to				= camera.target_translation;
-
-		let inertia			= (delta_seconds / 0.3).min(1.0);
-		camera_transform.translation = from.lerp(to, inertia);
+		camera_transform.translation += camera.velocity * delta_seconds;
+		camera.target_translation = camera_transform.translation;
 	}
 }
 
 pub fn fly_mode_mouse(
-		time: Res<Time>,
-	mut mouse_motion_event_reader: EventReader<MouseMotion>,
-	mut q_camera: Query<(&mut ReaderCamera, &mut Transform)>,
+		time						: Res<Time>,
+		mouse_button				: Res<Input<MouseButton>>,
+	mut mouse_motion_event_reader	: EventReader<MouseMotion>,
+	mut mouse_wheel_event_reader	: EventReader<MouseWheel>,
+	mut q_camera					: Query<(&mut ReaderCamera, &Camera, &mut Transform)>,
+	mut q_window					: Query<&mut Window, With<PrimaryWindow>>,
 ) {
 	let mut delta: Vec2 = Vec2::ZERO;
 	for event in mouse_motion_event_reader.iter() {
@@ -92,14 +212,48 @@ pub fn fly_mode_mouse(
 		return;
 	}
 
+	let wheel: f32 = mouse_wheel_event_reader.iter().map(|event| match event.unit {
+		MouseScrollUnit::Line => event.y,
+		MouseScrollUnit::Pixel => event.y / PIXELS_PER_LINE,
+	}).sum();
+
 	let delta_seconds = time.delta_seconds();
 
-	for (mut camera, mut transform) in q_camera.iter_mut() {
-		if camera.mode != CameraMode::Fly || !camera.enabled_rotation {
+	for (mut camera, camera_component, mut transform) in q_camera.iter_mut() {
+		// locked cursor stays with the camera that grabbed it
+		let pointer_input = camera.cursor_grabbed || camera_receives_pointer(q_window.get_single().ok(), camera_component);
+
+		let grab = camera.mode == CameraMode::Fly && camera.grab_cursor && camera.input_focus && mouse_button.pressed(MouseButton::Right) && pointer_input;
+
+		// released also when camera leaves fly mode to never leave cursor locked
+		if grab != camera.cursor_grabbed {
+			if let Ok(mut window) = q_window.get_single_mut() {
+				window.cursor.grab_mode	= if grab { CursorGrabMode::Locked } else { CursorGrabMode::None };
+				window.cursor.visible	= !grab;
+			}
+
+			camera.cursor_grabbed = grab;
+		}
+
+		if camera.mode != CameraMode::Fly {
 			continue;
 		}
-		camera.yaw			-= delta.x * camera.sensitivity * time.delta_seconds();
-		camera.pitch		+= delta.y * camera.sensitivity * time.delta_seconds();
+
+		// wheel up flies faster
+		if camera.input_focus && pointer_input && wheel != 0.0 {
+			camera.speed_scale = (camera.speed_scale * 1.1f32.powf(wheel)).clamp(0.1, 10.0);
+		}
+
+		let delta = if pointer_input && (!camera.grab_cursor || camera.cursor_grabbed) { delta } else { Vec2::ZERO };
+
+		if !camera.enabled_rotation {
+			continue;
+		}
+
+		let gamepad_look	= camera.gamepad_input.right * camera.gamepad.look_degrees_per_second * delta_seconds;
+
+		camera.yaw			-= delta.x * camera.sensitivity * MOUSE_REFERENCE_SECONDS + gamepad_look.x;
+		camera.pitch		+= delta.y * camera.sensitivity * MOUSE_REFERENCE_SECONDS - gamepad_look.y;
 
 		camera.pitch		= camera.pitch.clamp(-89.0, 89.9);
 
@@ -109,35 +263,61 @@ pub fn fly_mode_mouse(
 		let from			= transform.rotation;
 		let to				= Quat::from_axis_angle(Vec3::Y, yaw_radians) * Quat::from_axis_angle(-Vec3::X, pitch_radians);
 
-		let inertia			= (delta_seconds / camera.rotation_easing_seconds).min(1.0);
-		transform.rotation	= from.slerp(to, inertia);
+		let mut state		= camera.rotation_easing_state;
+		transform.rotation	= camera.easing(camera.rotation_easing).step(from, to, delta_seconds, &mut state);
+		camera.rotation_easing_state = state;
 	}
 }
 
+// touchpads report pixels, roughly this many make a wheel line
+const PIXELS_PER_LINE: f32 = 53.0;
+
 pub fn follow_mode_mouse(
 		time						: Res<Time>,
 	mut mouse_motion_event_reader	: EventReader<MouseMotion>,
 	mut mouse_wheel_event_reader	: EventReader<MouseWheel>,
-	mut q_camera					: Query<(&mut ReaderCamera, &mut Transform)>,
+	mut q_camera					: Query<(&mut ReaderCamera, &Camera, &mut Transform)>,
 		q_target					: Query<&Transform, Without<ReaderCamera>>,
+		q_window					: Query<&Window, With<PrimaryWindow>>,
 ) {
-	for (mut camera, mut camera_transform) in q_camera.iter_mut() {
+	// events can be read only once so we gather them for all cameras
+	let mut delta: Vec2 = Vec2::ZERO;
+	for event in mouse_motion_event_reader.iter() {
+		delta += event.delta;
+	}
+	if delta.is_nan() {
+		return;
+	}
+
+	let scroll_amounts: Vec<f32> = mouse_wheel_event_reader.iter().map(|event| {
+		// scale the event magnitude per pixel or per line
+		match event.unit {
+			MouseScrollUnit::Line => event.y,
+			MouseScrollUnit::Pixel => event.y / PIXELS_PER_LINE,
+		}
+	}).collect();
+
+	let window = q_window.get_single().ok();
+
+	for (mut camera, camera_component, mut camera_transform) in q_camera.iter_mut() {
 		if camera.mode != CameraMode::Follow {
 			continue
 		}
-		assert!(camera.target_entity != None);
 
-		let mut delta: Vec2 = Vec2::ZERO;
-		for event in mouse_motion_event_reader.iter() {
-			delta += event.delta;
-		}
-		if delta.is_nan() {
-			continue;
-		}
+		// pointer input goes to the camera under cursor
+		let pointer_input = camera_receives_pointer(window, camera_component);
+		let delta = if pointer_input { delta } else { Vec2::ZERO };
+		let scroll_amounts = if pointer_input { scroll_amounts.as_slice() } else { &[] };
+
+		// nothing to follow
+		let Some(target) = camera.target_entity else { continue };
+		let Ok(target_transform) = q_target.get(target) else { continue };
 
 		if camera.enabled_rotation {
-			camera.yaw -= delta.x * camera.sensitivity * time.delta_seconds();
-			camera.pitch += delta.y * camera.sensitivity * time.delta_seconds();
+			let gamepad_look = camera.gamepad_input.right * camera.gamepad.look_degrees_per_second * time.delta_seconds();
+
+			camera.yaw -= delta.x * camera.sensitivity * MOUSE_REFERENCE_SECONDS + gamepad_look.x;
+			camera.pitch += delta.y * camera.sensitivity * MOUSE_REFERENCE_SECONDS - gamepad_look.y;
 
 			camera.pitch = camera.pitch.clamp(-89.0, 89.9);
 		}
@@ -147,28 +327,18 @@ pub fn follow_mode_mouse(
 
 		//
 
-		let pixels_per_line = 53.0;
-		let mut scalar = 1.0;
-		for event in mouse_wheel_event_reader.iter() {
-			// scale the event magnitude per pixel or per line
-			let scroll_amount = match event.unit {
-				MouseScrollUnit::Line => event.y,
-				MouseScrollUnit::Pixel => event.y / pixels_per_line,
-			};
-			scalar *= 1.0 - scroll_amount * camera.zoom_sensitivity;
-		}
+		let scalar = scroll_amounts.iter().fold(1.0, |scalar, scroll_amount| scalar * (1.0 - scroll_amount * camera.zoom_sensitivity));
 
 		if camera.enabled_zoom {
-			camera.zoom = (scalar * camera.zoom)
+			let gamepad_zoom = camera.gamepad_input.triggers * camera.gamepad.zoom_per_second * time.delta_seconds();
+
+			camera.zoom = (scalar * camera.zoom - gamepad_zoom)
 				.min(100.0)
 				.max(1.0);
 		}
 
 		//
 		if camera.enabled_translation {
-			let target = camera.target_entity.unwrap();
-			let target_transform = q_target.get(target).unwrap();
-
 			camera_transform.translation = target_transform.translation + camera.zoom * unit_vector_from_yaw_and_pitch(yaw_radians, pitch_radians);
 		}
 
@@ -178,204 +348,627 @@ pub fn follow_mode_mouse(
 	}
 }
 
-use crate :: reader_mode as reader;
+/// Puts back transform of current mode so that mode systems don't see blending
+pub fn mode_transition_restore(
+	mut q_camera : Query<(&ReaderCamera, &mut Transform)>,
+) {
+	for (camera, mut transform) in q_camera.iter_mut() {
+		if let Some(transition) = camera.mode_transition.as_ref() {
+			*transform = transition.to;
+		}
+	}
+}
 
-pub fn reader_mode(
+pub fn mode_transition(
+		time							: Res<Time>,
+	mut mode_transition_finished_writer	: EventWriter<ModeTransitionFinished>,
+	mut q_camera						: Query<(Entity, &mut ReaderCamera, &mut Transform)>,
+		q_target						: Query<&Transform, Without<ReaderCamera>>,
+) {
+	for (camera_entity, mut camera, mut transform) in q_camera.iter_mut() {
+		if let Some(mode_from) = camera.mode_transition_requested.take() {
+			let presented			= camera.transform_presented.unwrap_or(*transform);
+			let target_translation	= camera.target_entity.and_then(|entity| q_target.get(entity).ok()).map(|target| target.translation);
+
+			// new mode continues from where the camera is, its output this frame was based on stale state so we don't use it
+			camera.init_mode_from(&presented, target_translation);
+			*transform				= presented;
+
+			let duration			= if camera.reduced_motion { camera.reduced_motion_easing.seconds() } else { camera.mode_transition_seconds };
+
+			camera.mode_transition	=
+			if mode_from != camera.mode && duration > 0.0 {
+				Some(ModeTransition { from: presented, to: presented, elapsed: 0.0, duration })
+			} else {
+				None
+			};
+
+			if mode_from != camera.mode && camera.mode_transition.is_none() {
+				mode_transition_finished_writer.send(ModeTransitionFinished { camera: camera_entity, mode: camera.mode });
+			}
+		}
+
+		if let Some(transition) = camera.mode_transition.as_mut() {
+			transition.to			= *transform;
+			transition.elapsed		+= time.delta_seconds();
+
+			let t					= (transition.elapsed / transition.duration).min(1.0);
+			let blend				= t * t * (3.0 - 2.0 * t);
+
+			transform.translation	= transition.from.translation.lerp(transition.to.translation, blend);
+			transform.rotation		= transition.from.rotation.slerp(transition.to.rotation, blend);
+
+			if t >= 1.0 {
+				camera.mode_transition = None;
+				mode_transition_finished_writer.send(ModeTransitionFinished { camera: camera_entity, mode: camera.mode });
+			}
+		}
+
+		camera.transform_presented	= Some(*transform);
+	}
+}
+
+pub fn projection_transition_restore(
+	mut q_camera : Query<(&ReaderCamera, &mut Transform, &mut Projection)>,
+) {
+	for (camera, mut transform, mut projection) in q_camera.iter_mut() {
+		if let Some(transition) = camera.projection_transition.as_ref() {
+			transform.translation	-= transition.dolly;
+			*projection				= transition.to.clone();
+		}
+	}
+}
+
+// perspective fov at the orthographic end of dolly zoom, the smaller the further camera has to go
+const DOLLY_FOV_MIN: f32 = 0.02;
+
+pub fn projection_transition(
+		time		: Res<Time>,
+	mut q_camera	: Query<(&mut ReaderCamera, &mut Transform, &mut Projection, &Camera)>,
+		q_target	: Query<&Transform, Without<ReaderCamera>>,
+) {
+	for (mut camera, mut transform, mut projection, camera_component) in q_camera.iter_mut() {
+		// projection without dolly zoom as modes left it this frame
+		if let Some(transition) = camera.projection_transition.as_mut() {
+			transition.to			= projection.clone();
+		} else if !camera.projection_transition_requested {
+			// app may set projection directly
+			camera.perspective		= matches!(*projection, Projection::Perspective(_));
+		}
+
+		let target_translation		= camera.target_entity.and_then(|entity| q_target.get(entity).ok()).map(|target| target.translation);
+
+		if camera.projection_transition_requested {
+			camera.projection_transition_requested = false;
+
+			let duration			= if camera.reduced_motion { camera.reduced_motion_easing.seconds() } else { camera.projection_transition_seconds };
+
+			// switching back halfway through continues from where the camera is
+			let (perspective, elapsed) = match (camera.projection_transition.take(), &*projection) {
+				(Some(transition), _)						=> (transition.perspective, (transition.duration - transition.elapsed).max(0.0) * duration / transition.duration),
+				(None, Projection::Perspective(perspective)) => (perspective.clone(), 0.0),
+				(None, Projection::Orthographic(_))			=> (PerspectiveProjection::default(), 0.0),
+			};
+
+			let to =
+			if camera.perspective {
+				Projection::Perspective(perspective.clone())
+			} else {
+				let distance			= camera.focus_distance(&transform, target_translation);
+
+				let mut orthographic	= OrthographicProjection { scaling_mode: ScalingMode::FixedVertical(2.0), ..default() };
+				if let Some(size) = camera_component.logical_viewport_size() {
+					orthographic.update(size.x, size.y);
+				}
+
+				Projection::Orthographic(orthographic_with_height(&orthographic, perspective_height(perspective.fov, distance)))
+			};
+
+			camera.projection_transition =
+			if duration > 0.0 {
+				Some(ProjectionTransition { to, perspective, dolly: Vec3::ZERO, elapsed, duration })
+			} else {
+				*projection = to;
+				None
+			};
+		}
+
+		let Some(mut transition) = camera.projection_transition.take() else { continue };
+
+		transition.elapsed			+= time.delta_seconds();
+
+		let t						= (transition.elapsed / transition.duration).min(1.0);
+		if t >= 1.0 {
+			*projection				= transition.to;
+			continue
+		}
+
+		let blend					= t * t * (3.0 - 2.0 * t);
+		let orthographic			= if camera.perspective { 1.0 - blend } else { blend };
+
+		// fov narrows evenly on log scale while camera backs off to keep focus plane the same size
+		let tan_half				= (transition.perspective.fov / 2.0).tan();
+		let tan_half_blended		= tan_half * ((DOLLY_FOV_MIN / 2.0).tan() / tan_half).powf(orthographic);
+
+		let distance				= camera.focus_distance(&transform, target_translation);
+		let dolly_distance			= distance * (tan_half / tan_half_blended - 1.0);
+
+		transition.dolly			= transform.back() * dolly_distance;
+		transform.translation		+= transition.dolly;
+
+		*projection = Projection::Perspective(PerspectiveProjection {
+			fov						: 2.0 * tan_half_blended.atan(),
+			far						: transition.perspective.far + dolly_distance,
+			..transition.perspective.clone()
+		});
+
+		camera.projection_transition = Some(transition);
+	}
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn orbit_mode(
 		time						: Res<Time>,
-		key							: Res<Input<KeyCode>>,
+		mouse_button				: Res<Input<MouseButton>>,
 		mouse_motion_event_reader	: EventReader<MouseMotion>,
 		mouse_wheel_event_reader	: EventReader<MouseWheel>,
-	mut q_camera					: Query<(Entity, &mut ReaderCamera, &Projection)>,
-		q_text_descriptor			: Query<&TextDescriptor>,
-	mut	q_transform					: Query<&mut Transform>,
+	mut camera_command_reader		: EventReader<ReaderCameraCommand>,
+	mut q_camera					: Query<(Entity, &mut ReaderCamera, &Camera, &Projection, &mut Transform)>,
+		q_bounds					: Query<(&GlobalTransform, Option<&Aabb>, Option<&TextDescriptor>, Option<&RowHeights>, Option<&RowMap>)>,
+		q_window					: Query<&Window, With<PrimaryWindow>>,
 ) {
-	let pixels_per_line = 20.0;
-
-	let key_scroll_state =
-	if key.pressed(KeyCode::Up) && !key.just_pressed(KeyCode::Up) {
-		Some(KeyScroll::Up)
-	} else if key.pressed(KeyCode::Down) && !key.just_pressed(KeyCode::Down) {
-		Some(KeyScroll::Down)
-	} else {
-		None
-	};
-
-	let Ok((camera_entity, mut camera, camera_projection)) = q_camera.get_single_mut() else {
-		return
-	};
+	// events can be read only once so we gather them for all cameras
+	let delta_mouse			= delta_mouse_from_events(mouse_motion_event_reader);
+	let (lines, pixels)		= delta_wheel_from_events(mouse_wheel_event_reader);
+	let delta_wheel			= lines.map_or(0.0, |lines| lines.y) + pixels.map_or(0.0, |pixels| pixels.y) / PIXELS_PER_LINE;
+	let camera_commands		: Vec<ReaderCameraCommand> = camera_command_reader.iter().copied().collect();
 
-	if camera.mode != CameraMode::Reader {
-		return
+	let window				= q_window.get_single().ok();
+	let delta_seconds		= time.delta_seconds();
+
+	for (camera_entity, mut camera, camera_component, projection, mut transform) in q_camera.iter_mut() {
+		if camera.mode != CameraMode::Orbit {
+			continue
+		}
+
+		// camera spawned in orbit mode never went through set_mode, so orbit around whatever it looks at
+		if camera.orbit_state.yaw_pitch_distance.z == 0.0 {
+			let target_translation = camera.target_entity.and_then(|entity| q_bounds.get(entity).ok()).map(|(target_transform, ..)| target_transform.translation());
+			camera.init_mode_from(&transform, target_translation);
+		}
+
+		let control			= camera.orbit;
+		let gamepad			= camera.gamepad_input;
+		let mut state		= camera.orbit_state;
+
+		for command in camera_commands.iter().filter(|command| command.camera == camera_entity) {
+			let CameraCommand::Focus(entity) = command.command else { continue };
+			let Ok((entity_transform, aabb, text_descriptor, row_heights, row_map)) = q_bounds.get(entity) else { continue };
+
+			let (center, radius) = entity_bounds(entity_transform, aabb, text_descriptor, row_heights, row_map);
+			let fov			= match projection {
+				Projection::Perspective(perspective) => perspective.fov,
+				Projection::Orthographic(_) => PerspectiveProjection::default().fov,
+			};
+
+			state.focus(center, radius, fov, &control);
+		}
+
+		let pointer_input	= camera_receives_pointer(window, camera_component);
+		let rotate_held		= match control.rotate_button {
+			Some(button)	=> mouse_button.pressed(button),
+			None			=> true,
+		};
+
+		if camera.enabled_rotation {
+			let gamepad_look = gamepad.right * camera.gamepad.look_degrees_per_second * delta_seconds;
+			state.rotate(-gamepad_look.x, -gamepad_look.y, &control);
+
+			if pointer_input && rotate_held {
+				state.rotate(-delta_mouse.x * control.rotate_sensitivity, delta_mouse.y * control.rotate_sensitivity, &control);
+			}
+		}
+
+		if camera.enabled_translation {
+			// pivot follows the mouse so that content moves together with the cursor
+			state.pan(-gamepad.left * control.gamepad_pan_per_second * delta_seconds, transform.rotation);
+
+			if pointer_input && mouse_button.pressed(control.pan_button) {
+				state.pan(Vec2::new(-delta_mouse.x, delta_mouse.y) * control.pan_sensitivity, transform.rotation);
+			}
+		}
+
+		if camera.enabled_zoom {
+			// right trigger zooms in, zoom_per_second is distance change like in reader mode
+			let gamepad_zoom = gamepad.triggers * camera.gamepad.zoom_per_second * delta_seconds;
+			state.zoom(1.0 - gamepad_zoom / state.target_yaw_pitch_distance.z.max(f32::EPSILON), &control);
+
+			if pointer_input && delta_wheel != 0.0 {
+				state.zoom(1.0 + delta_wheel * control.zoom_sensitivity, &control);
+			}
+		}
+
+		state.update(camera.easing(control.easing), delta_seconds);
+
+		let orbit_transform	= state.transform();
+		transform.translation = orbit_transform.translation;
+		transform.rotation	= orbit_transform.rotation;
+
+		camera.orbit_state	= state;
+	}
+}
+
+use crate :: reader_mode as reader;
+
+/// App tells us where it scrolled to after our requests, done for every mode to not lose acks
+pub fn row_offset_ack(
+	mut row_offset_ack_reader	: EventReader<RowOffsetAck>,
+	mut q_camera				: Query<&mut ReaderCamera>,
+) {
+	for ack in row_offset_ack_reader.iter() {
+		if let Ok(mut camera) = q_camera.get_mut(ack.camera) {
+			camera.acknowledge_row_offset(ack.row_offset);
+		}
 	}
+}
 
-	let Some(camera_target_entity) = camera.target_entity else {
+pub fn rows_edited(
+	mut rows_edited_reader			: EventReader<RowsEdited>,
+	mut row_offset_changed_writer	: EventWriter<RowOffsetChanged>,
+	mut q_camera					: Query<(Entity, &mut ReaderCamera)>,
+) {
+	let edits : Vec<RowsEdited> = rows_edited_reader.iter().copied().collect();
+	if edits.is_empty() {
 		return
-	};
+	}
 
-	let delta_wheel			= delta_wheel_from_events(pixels_per_line, mouse_wheel_event_reader);
-	let delta_mouse			= delta_mouse_from_events(mouse_motion_event_reader);
-	let mut delta_seconds	= time.delta_seconds();
+	for (camera_entity, mut camera) in q_camera.iter_mut() {
+		let row_offset_prev = camera.row_offset_out();
+		let target			= camera.target_entity;
 
-	let wheel_event_occurred = delta_wheel.is_some();
+		for edit in edits.iter().filter(|edit| Some(edit.target) == target) {
+			camera.edit_rows(edit);
+		}
 
-	if !wheel_event_occurred {
-		camera.scroll_idle_timer.tick(time.delta());
-	} else {
-		camera.scroll_idle_timer.reset();
+		// reported right away so app never sees offset of the text before the edit
+		let row_offset = camera.row_offset_out();
+		if row_offset != row_offset_prev {
+			row_offset_changed_writer.send(RowOffsetChanged { camera: camera_entity, row_offset });
+		}
 	}
+}
 
-	// "make believe" fps for dormant state to avoid jitter when fps is too low
-	if !camera.is_awake() {
-		delta_seconds		= 1. / 60.;
+/// Keeps a copy of target's RowMap on every camera so that row offsets can be translated without access to target
+pub fn row_map_sync(
+	mut q_camera	: Query<&mut ReaderCamera>,
+		q_row_map	: Query<Ref<RowMap>>,
+) {
+	for mut camera in q_camera.iter_mut() {
+		let target		= camera.target_entity;
+		let row_map		= target.and_then(|entity| q_row_map.get(entity).ok());
+		let source		= row_map.as_ref().and(target);
+
+		match row_map {
+			Some(row_map) if row_map.is_changed() || camera.row_map_source != source => {
+				camera.set_row_map(row_map.clone());
+			},
+			None if camera.row_map_source.is_some() => {
+				camera.set_row_map(RowMap::default());
+			},
+			_ => {},
+		}
+
+		if camera.row_map_source != source {
+			camera.row_map_source = source;
+		}
 	}
+}
 
-	let text_descriptor		= q_text_descriptor.get(camera_target_entity).unwrap();
+#[allow(clippy::too_many_arguments)]
+pub fn reader_mode(
+		time						: Res<Time>,
+		key							: Res<Input<KeyCode>>,
+		mouse_button				: Res<Input<MouseButton>>,
+		mouse_motion_event_reader	: EventReader<MouseMotion>,
+		mouse_wheel_event_reader	: EventReader<MouseWheel>,
+		touches						: Res<Touches>,
+	mut row_scroll_request_writer	: EventWriter<RowScrollRequest>,
+	mut row_offset_changed_writer	: EventWriter<RowOffsetChanged>,
+	mut camera_command_reader		: EventReader<ReaderCameraCommand>,
+	mut q_camera					: Query<(Entity, &mut ReaderCamera, &mut Projection, &Camera)>,
+		q_text_descriptor			: Query<(&TextDescriptor, Option<&RowHeights>)>,
+	mut	q_transform					: Query<&mut Transform>,
+		q_window					: Query<&Window, With<PrimaryWindow>>,
+) {
+	// events can be read only once so we gather them for all cameras
+	// shift turns vertical wheel into horizontal like in most text views
+	let shift_pressed		= key.any_pressed([KeyCode::LShift, KeyCode::RShift]);
+	let shift_wheel			= |wheel: Vec2| if shift_pressed { Vec2::new(wheel.x + wheel.y, 0.0) } else { wheel };
+	let (delta_wheel_lines, delta_wheel_pixels) = delta_wheel_from_events(mouse_wheel_event_reader);
+	let delta_wheel_2d		= delta_wheel_lines.map(shift_wheel);
+	let delta_touchpad_2d	= delta_wheel_pixels.map(shift_wheel);
+	let delta_mouse			= delta_mouse_from_events(mouse_motion_event_reader);
+	let camera_commands		: Vec<ReaderCameraCommand> = camera_command_reader.iter().copied().collect();
 
-	let row_max				= text_descriptor.rows as f32;
+	let window				= q_window.get_single().ok();
 
-	let visible_rows		= if let Some(rows) = camera.visible_rows_target { rows } else { camera.visible_rows };
-	let visible_rows_half	= visible_rows / 2.0;
+	for (camera_entity, mut camera, mut camera_projection, camera_component) in q_camera.iter_mut() {
+		// row commands are kept until we know which text the camera is looking at
+		let mut row_commands = Vec::new();
+		for command in camera_commands.iter().filter(|command| command.camera == camera_entity) {
+			if !reader::command_generic(&command.command, &mut camera) {
+				row_commands.push(command.command);
+			}
+		}
 
-	let target_row			= camera.row_constant_offset + camera.row_offset_app as f32 + visible_rows_half;
+		if camera.mode != CameraMode::Reader {
+			continue
+		}
 
-	let row_changed			= target_row != camera.target_row_prev;
-	let row_delta			= target_row - camera.target_row_prev;
+		let Some(camera_target_entity) = camera.target_entity else {
+			continue
+		};
 
-	camera.target_row_prev	= target_row;
+		let Ok((text_descriptor, row_heights)) = q_text_descriptor.get(camera_target_entity) else {
+			continue
+		};
 
-	let text_start_reached	= camera.row_offset_app == 0;
-	let text_end_reached	= target_row.ceil() + 1.0 >= row_max;
+		let row_layout			= RowLayout::new(text_descriptor, row_heights);
+
+		// pointer input goes to the camera under cursor, keyboard to the focused one
+		let pointer_input		= camera_receives_pointer(window, camera_component);
+
+		let delta_wheel_2d		= if pointer_input { delta_wheel_2d } else { None };
+		let delta_wheel			= delta_wheel_2d.map(|wheel| wheel.y).filter(|wheel| *wheel != 0.0);
+		let delta_wheel_swipe	= delta_wheel_2d.map(|wheel| wheel.x).filter(|wheel| *wheel != 0.0);
+		let delta_touchpad_2d	= if pointer_input { delta_touchpad_2d } else { None };
+		let delta_mouse			= if pointer_input { delta_mouse } else { Vec2::ZERO };
+		// first press is a ScrollBy command from camera_actions, holding scrolls continuously
+		let held = |action| camera.actions.pressed(action, &key, &mouse_button) && !camera.actions.just_pressed(action, &key, &mouse_button);
+		let key_scroll_state	=
+		if !camera.input_focus {
+			None
+		} else if held(CameraAction::ScrollLineUp) {
+			Some(KeyScroll::Up)
+		} else if held(CameraAction::ScrollLineDown) {
+			Some(KeyScroll::Down)
+		} else {
+			None
+		};
+		let delta_seconds		= time.delta_seconds();
 
-	let rows_meta = reader::RowsMetaData {
-		row_changed,
-		row_delta,
-		row_max,
-		target_row,
-		visible_rows,
-		visible_rows_half,
-		text_start_reached,
-		text_end_reached
-	};
+		// world units per logical pixel on the text surface to make text follow fingers exactly
+		let viewport_rect		= camera_component.logical_viewport_rect();
+		let viewport_height		= viewport_rect.map_or(0.0, |(min, max)| max.y - min.y);
+		let world_per_pixel		= if viewport_height > 0.0 { camera.visible_height / viewport_height } else { 0.0 };
 
-	// always look at center for now
-	camera.column = (text_descriptor.columns / 2) as usize;
+		let mut touch_state		= camera.touch_state;
+		camera.touch_gesture	= touch_gesture(&touches, viewport_rect, world_per_pixel, &mut touch_state);
+		camera.touch_state		= touch_state;
 
-	//
-	// Calculating camera transform for given row and column
-	//
+		// touchpad moves text by the same amount of pixels fingers moved
+		let delta_touchpad		= delta_touchpad_2d.map(|pixels| pixels * world_per_pixel);
 
-	// a copy to not have another read access to q_transform
-	let target_entity_transform = q_transform.get(camera_target_entity).unwrap().clone();
+		let wheel_event_occurred = delta_wheel_2d.is_some() || delta_touchpad.is_some() || key_scroll_state.is_some() || camera.gamepad_input.is_active() || camera.touch_gesture.fingers > 0 || camera.momentum_state.is_active();
 
-	// contains expected transform for given row and column without any postprocessing
-	let mut camera_transform = q_transform.get_mut(camera_entity).unwrap();
+		if !wheel_event_occurred {
+			camera.scroll_idle_timer.tick(time.delta());
+		} else {
+			camera.scroll_idle_timer.reset();
+		}
 
-	if let Some(wheel) = delta_wheel {
-		reader::zoom(
-			wheel,
-			&mut camera
-		);
-	}
+		for command in row_commands.iter() {
+			reader::command_rows(command, text_descriptor, &row_layout, &mut camera);
+		}
 
-	let apply_zoom = camera.is_zooming();
-	if apply_zoom {
-		reader::apply_zoom(delta_seconds, &mut camera);
-	}
+		reader::caret_follow(text_descriptor, &row_layout, &mut camera);
 
-	let pitch_compensation = reader::rotation(
-		delta_seconds,
-		delta_mouse.y,
-		&rows_meta,
-		&mut camera
-	);
+		reader::tail_follow(text_descriptor, &row_layout, &mut camera);
 
-	reader::apply_rotation(delta_seconds, &camera, &mut camera_transform);
+		let row_max				= reader::visual_row_count(text_descriptor, &camera) as f32;
 
-	reader::translation(
-		key_scroll_state,
-		delta_wheel,
-		delta_mouse,
-		delta_seconds,
+		let visible_rows		= if let Some(rows) = camera.visible_rows_target { rows } else { camera.visible_rows };
+		let visible_rows_half	= visible_rows / 2.0;
+		let visible_height_half	= if let Some(height) = camera.visible_height_target { height } else { camera.visible_height } / 2.0;
+
+		// rows edited above the viewport move camera right away, the same content stays under it
+		if camera.row_anchor_pending.is_some() {
+			let mut camera_transform = q_transform.get_mut(camera_entity).unwrap();
+			reader::apply_row_anchor(visible_height_half, text_descriptor, &row_layout, &mut camera, &mut camera_transform);
+		}
 
-		pitch_compensation,
-		&rows_meta,
+		let first_row			= camera.row_constant_offset + camera.row_offset_app as f32;
+		let target_row			= row_layout.row_at(row_layout.top(first_row) + visible_height_half);
 
-		text_descriptor,
-		&target_entity_transform,
-		&mut camera,
-	);
+		let row_changed			= target_row != camera.target_row_prev;
+		let row_delta			= target_row - camera.target_row_prev;
+
+		camera.target_row_prev	= target_row;
+
+		let text_start_reached	= camera.row_offset_app == 0;
+		let text_end_reached	= target_row.ceil() + 1.0 >= row_max;
+
+		let rows_meta = reader::RowsMetaData {
+			row_changed,
+			row_delta,
+			row_max,
+			first_row,
+			visible_rows,
+			visible_rows_half,
+			visible_height_half,
+			text_start_reached,
+			text_end_reached
+		};
 
-	reader::apply_translation(delta_seconds, &rows_meta, &camera, &mut camera_transform);
+		//
+		// Calculating camera transform for given row and column
+		//
+
+		// a copy to not have another read access to q_transform
+		let target_entity_transform = q_transform.get(camera_target_entity).unwrap().clone();
+
+		// contains expected transform for given row and column without any postprocessing
+		let mut camera_transform = q_transform.get_mut(camera_entity).unwrap();
+
+		if let Some(wheel) = delta_wheel {
+			reader::zoom(
+				wheel,
+				&mut camera
+			);
+		}
+
+		if let Some(pinch) = camera.touch_gesture.pinch {
+			let center = camera.touch_gesture.center;
+			let center = Vec2::new(center.x, center.y * reader::input_direction(text_descriptor));
+			reader::pinch_zoom(pinch, center, &mut camera);
+		}
+
+		// right trigger zooms in
+		if camera.gamepad_input.triggers != 0.0 {
+			let zoom_scalar = -camera.gamepad_input.triggers * camera.gamepad.zoom_per_second * delta_seconds;
+			reader::zoom(zoom_scalar, &mut camera);
+		}
+
+		let apply_zoom = camera.is_zooming();
+		if apply_zoom {
+			reader::apply_zoom(delta_seconds, &mut camera);
+		}
+
+		// orthographic projection zooms with scale, camera still moves along z to keep clipping and mode transitions sane
+		reader::apply_projection(&camera, &mut camera_projection);
+
+		let pitch_compensation = reader::rotation(
+			delta_seconds,
+			delta_mouse.y,
+			&rows_meta,
+			&mut camera
+		);
+
+		reader::apply_rotation(delta_seconds, &mut camera, &mut camera_transform);
+
+		reader::translation(
+			key_scroll_state,
+			delta_wheel,
+			delta_wheel_swipe,
+			delta_touchpad,
+			delta_mouse,
+			delta_seconds,
+
+			pitch_compensation,
+			&rows_meta,
 
-	// To keep camera looking at the same row when zooming we add some extra scrolling
-	if apply_zoom {
-		reader::zoom_adjustment(
 			text_descriptor,
+			&row_layout,
 			&target_entity_transform,
-			camera_projection,
-			&mut camera_transform,
-			&mut camera
+			&mut camera,
 		);
-	}
 
-	// Now we calculate the actual row offset we're looking at currently
+		reader::apply_translation(delta_seconds, &rows_meta, &mut camera, &mut camera_transform);
+
+		// To keep camera looking at the same row when zooming we add some extra scrolling
+		if apply_zoom {
+			reader::zoom_adjustment(
+				text_descriptor,
+				&row_layout,
+				&target_entity_transform,
+				&camera_projection,
+				&mut camera_transform,
+				&mut camera
+			);
+		}
+
+		// Now we calculate the actual row offset we're looking at currently
 
-	let row_offset_out = ((camera_transform.translation.y / text_descriptor.glyph_height).abs() - camera.row_constant_offset - rows_meta.visible_rows_half).round();
+		camera.row_anchor = row_layout.row_at(camera_transform.translation.y.abs() - rows_meta.visible_height_half) - camera.row_constant_offset;
+
+		let row_offset_out = camera.row_anchor.round().max(0.0) as u32;
+		if row_offset_out != camera.row_offset_camera {
+			row_offset_changed_writer.send(RowOffsetChanged { camera: camera_entity, row_offset: row_offset_out });
+		}
 
-	camera.row_offset_camera = row_offset_out.max(0.0) as u32;
+		camera.row_offset_camera = row_offset_out;
+
+		let rows = camera.row_offset_delta_unsent();
+		if rows != 0 {
+			row_scroll_request_writer.send(RowScrollRequest { camera: camera_entity, rows });
+		}
+	}
 }
 
 pub fn calc_frustum_data(
 	mut q_camera : Query<(Entity, &mut ReaderCamera, &Projection)>,
-		q_text_descriptor : Query<&TextDescriptor>,
+		q_text_descriptor : Query<(&TextDescriptor, Option<&RowHeights>)>,
 		q_transform : Query<&Transform>,
+	mut visible_rows_changed_writer : EventWriter<VisibleRowsChanged>,
 ) {
-	let Ok((camera_entity, mut camera_reader, camera_projection)) = q_camera.get_single_mut() else { return };
+	for (camera_entity, mut camera_reader, camera_projection) in q_camera.iter_mut() {
+		// zooming out during fly-over jump would make app load every row on the way
+		if camera_reader.mode != CameraMode::Reader || camera_reader.is_jumping() {
+			continue;
+		}
 
-	if camera_reader.mode != CameraMode::Reader {
-		return;
-	}
+		let Some(target_entity) = camera_reader.target_entity else { continue };
+
+		let Ok((text_descriptor, row_heights)) = q_text_descriptor.get(target_entity) else { continue };
+		let Ok(target_entity_transform) = q_transform.get(target_entity) else { continue };
 
-	let Some(target_entity) = camera_reader.target_entity else { return };
+		let target_entity_z = target_entity_transform.translation.z;
 
-	let text_descriptor = q_text_descriptor.get(target_entity).unwrap();
-	let target_entity_transform = q_transform.get(target_entity).unwrap();
+		let mut camera_transform_z_only = q_transform.get(camera_entity).unwrap().clone();
+		let camera_y = camera_transform_z_only.translation.y;
+		// we remove x and y since the visible height should not depend on how much we scrolled, just how far the camera is from the surface with text
+		camera_transform_z_only.translation = Vec3::Z * camera_transform_z_only.translation.z;
 
-	let target_entity_z = target_entity_transform.translation.z;
+		// calculating frustum manually for now because using cache introduces small desync between frustum and camera position
+		let frustum = calc_frustum(&camera_transform_z_only, camera_projection);
 
-	let mut camera_transform_z_only = q_transform.get(camera_entity).unwrap().clone();
-	// we remove x and y since the amount of visible rows should not depend on how much we scrolled, just how far the camera is from the surface with text
-	camera_transform_z_only.translation = Vec3::Z * camera_transform_z_only.translation.z;
+		//
 
-	// calculating frustum manually for now because using cache introduces small desync between frustum and camera position
-	let frustum = calc_frustum(&camera_transform_z_only, camera_projection);
+		let row_layout = RowLayout::new(text_descriptor, row_heights);
+		let column_width = text_descriptor.glyph_width;
 
-	//
+		camera_reader.y_top		= calc_frustum_y_border(&frustum, target_entity_z, true);
+		camera_reader.y_bottom	= calc_frustum_y_border(&frustum, target_entity_z, false);
 
-	let row_height = text_descriptor.glyph_height;
-	let column_width = text_descriptor.glyph_width;
+		let visible_rows_prev = camera_reader.visible_rows;
+		let visible_height_prev = camera_reader.visible_height;
 
-	camera_reader.y_top		= calc_frustum_y_border(&frustum, target_entity_z, true);
-	camera_reader.y_bottom	= calc_frustum_y_border(&frustum, target_entity_z, false);
+		camera_reader.visible_height = camera_reader.y_top - camera_reader.y_bottom;
 
-	let visible_rows_prev = camera_reader.visible_rows;
+		// rows can differ in height so their amount depends on where the top edge of viewport is
+		let top = camera_y.abs() - camera_reader.visible_height / 2.0;
+		camera_reader.visible_rows = row_layout.rows_in(top, camera_reader.visible_height);
 
-	camera_reader.visible_rows = (camera_reader.y_top - camera_reader.y_bottom) / row_height; // calc_visible_rows(&frustum, target_object_z, row_height);
+		// targets wait for zoom to finish, rows keep changing while scrolling through rows of different height
+		if camera_reader.visible_height_target.is_none() || visible_height_prev == camera_reader.visible_height {
+			camera_reader.visible_height_target = Some(camera_reader.visible_height);
+			camera_reader.visible_rows_target = Some(camera_reader.visible_rows);
+		}
 
-	if camera_reader.visible_rows_target.is_none() || visible_rows_prev == camera_reader.visible_rows {
-		camera_reader.visible_rows_target = Some(camera_reader.visible_rows);
-	}
+		//
 
-	//
+		camera_reader.x_left	= calc_frustum_x_border(&frustum, target_entity_z, false);
+		camera_reader.x_right	= calc_frustum_x_border(&frustum, target_entity_z, true);
 
-	camera_reader.x_left	= calc_frustum_x_border(&frustum, target_entity_z, false);
-	camera_reader.x_right	= calc_frustum_x_border(&frustum, target_entity_z, true);
+		let visible_columns_prev = camera_reader.visible_columns;
 
-	let visible_columns_prev = camera_reader.visible_columns;
+		camera_reader.visible_columns = (camera_reader.x_right - camera_reader.x_left) / column_width; // calc_visible_columns(&frustum, target_object_z, column_width);
 
-	camera_reader.visible_columns = (camera_reader.x_right - camera_reader.x_left) / column_width; // calc_visible_columns(&frustum, target_object_z, column_width);
+		// columns become known on the first frame, camera starts at the center of text once and keeps whatever column is set after
+		if camera_reader.visible_columns_target.is_none() {
+			camera_reader.column = text_descriptor.columns / 2;
+		}
+
+		if camera_reader.visible_columns_target.is_none() || visible_columns_prev == camera_reader.visible_columns {
+			camera_reader.visible_columns_target = Some(camera_reader.visible_columns);
+		}
 
-	if camera_reader.visible_columns_target.is_none() || visible_columns_prev == camera_reader.visible_columns {
-		camera_reader.visible_columns_target = Some(camera_reader.visible_columns);
+		if visible_rows_prev != camera_reader.visible_rows || visible_columns_prev != camera_reader.visible_columns {
+			visible_rows_changed_writer.send(VisibleRowsChanged {
+				camera			: camera_entity,
+				visible_rows	: camera_reader.visible_rows,
+				visible_columns	: camera_reader.visible_columns,
+			});
+		}
 	}
 }
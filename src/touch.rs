@@ -0,0 +1,75 @@
+use bevy :: prelude :: *;
+use bevy :: input :: touch :: Touches;
+
+// Touch positions come in logical pixels with top left origin, everything here is converted to world units
+// on the text surface with y pointing down so that positive values mean "towards the end of text"
+
+#[derive(Default, Clone, Copy, Debug)]
+pub(crate) struct TouchState {
+	pub(crate) fingers			: usize,
+	pub(crate) center			: Vec2,
+	pub(crate) spread			: f32,
+}
+
+#[derive(Default, Clone, Copy, Debug)]
+pub(crate) struct TouchGesture {
+	pub(crate) fingers			: usize,
+	/// movement of the fingers center since last frame
+	pub(crate) drag				: Vec2,
+	/// zoom multiplier from two finger pinch, below 1.0 when fingers move apart
+	pub(crate) pinch			: Option<f32>,
+	/// fingers center relative to viewport center
+	pub(crate) center			: Vec2,
+	/// the only finger was lifted this frame
+	pub(crate) released			: bool,
+}
+
+pub(crate) fn touch_gesture(
+	touches				: &Touches,
+	viewport			: Option<(Vec2, Vec2)>,
+	world_per_pixel		: f32,
+	state				: &mut TouchState,
+) -> TouchGesture {
+	// only touches that started inside viewport belong to this camera
+	let inside = |position: Vec2| match viewport {
+		Some((min, max)) => position.cmpge(min).all() && position.cmple(max).all(),
+		None => true,
+	};
+
+	let positions: Vec<Vec2> = touches.iter()
+		.filter(|touch| inside(touch.start_position()))
+		.map(|touch| touch.position())
+		.collect();
+
+	let fingers			= positions.len();
+	let center			= if fingers > 0 { positions.iter().sum::<Vec2>() / fingers as f32 } else { Vec2::ZERO };
+	let spread			= if fingers == 2 { positions[0].distance(positions[1]) } else { 0.0 };
+
+	let viewport_center	= match viewport {
+		Some((min, max)) => (min + max) / 2.0,
+		None => Vec2::ZERO,
+	};
+
+	let mut gesture		= TouchGesture {
+		fingers,
+		center			: (center - viewport_center) * world_per_pixel,
+		..default()
+	};
+
+	// amount of fingers changed so there is nothing to compare to
+	if fingers == state.fingers && fingers > 0 {
+		gesture.drag	= (center - state.center) * world_per_pixel;
+
+		if fingers == 2 && spread > 0.0 && state.spread > 0.0 {
+			gesture.pinch = Some(state.spread / spread);
+		}
+	}
+
+	gesture.released	= fingers == 0 && state.fingers == 1;
+
+	state.fingers		= fingers;
+	state.center		= center;
+	state.spread		= spread;
+
+	gesture
+}
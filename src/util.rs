@@ -3,21 +3,6 @@ use bevy :: prelude :: *;
 use bevy :: render	:: { camera :: { * }, primitives :: Frustum };
 use bevy :: input	:: mouse :: { MouseScrollUnit, MouseWheel, MouseMotion };
 
-pub fn movement_axis(
-	input: &Res<Input<KeyCode>>,
-	plus: KeyCode,
-	minus: KeyCode,
-) -> f32 {
-	let mut axis = 0.0;
-	if input.pressed(plus) {
-		axis += 1.0;
-	}
-	if input.pressed(minus) {
-		axis -= 1.0;
-	}
-	axis
-}
-
 pub fn forward_vector(rotation: &Quat) -> Vec3 {
 	rotation.mul_vec3(Vec3::Z).normalize()
 }
@@ -43,6 +28,30 @@ pub fn unit_vector_from_yaw_and_pitch(yaw: f32, pitch: f32) -> Vec3 {
     Mat3::from_axis_angle(pitch_axis, pitch) * ray
 }
 
+/// Orthographic projection scaled to show given height. Area is scaled too because it is only
+/// recalculated from scale in PostUpdate and frustum has to be up to date this frame.
+pub fn orthographic_with_height(
+	orthographic		: &OrthographicProjection,
+	height				: f32,
+) -> OrthographicProjection {
+	let height_current	= orthographic.area.height();
+	let mut orthographic = orthographic.clone();
+	if height_current <= 0.0 || height <= 0.0 {
+		return orthographic
+	}
+
+	let ratio			= height / height_current;
+	orthographic.scale	*= ratio;
+	orthographic.area	= Rect::from_corners(orthographic.area.min * ratio, orthographic.area.max * ratio);
+
+	orthographic
+}
+
+/// Height perspective projection with given fov shows at given distance
+pub fn perspective_height(fov: f32, distance: f32) -> f32 {
+	2.0 * distance * (fov / 2.0).tan()
+}
+
 pub fn calc_frustum(
 	camera_transform	: &Transform,
 	camera_projection	: &Projection,
@@ -73,11 +82,18 @@ pub enum FrustumPlane {
 //	Far		= 5
 }
 
+// FIXME: add z offset for glyphs as a camera parameter? maybe to text descriptor
+pub const GLYPH_Z_OFFSET: f32 = 0.05;
+
+// mouse deltas are distances already, scaling them by frame time would make the speed depend on frame rate,
+// so they are scaled by this instead to keep the speed they had at 60 fps
+pub const MOUSE_REFERENCE_SECONDS: f32 = 1.0 / 60.0;
+
 // plane equation by three vertices
 // Ax + By + Cz + D = 0
 
 pub fn calc_frustum_y_border(frustum: &Frustum, z_in: f32, top: bool) -> f32 {
-	let z = z_in + 0.05; // FIXME: add z offset for glyphs as a camera parameter? maybe to text descriptor
+	let z = z_in + GLYPH_Z_OFFSET;
 
 	let plane_index = if top { FrustumPlane::Top } else { FrustumPlane::Bottom } as usize;
 
@@ -87,7 +103,7 @@ pub fn calc_frustum_y_border(frustum: &Frustum, z_in: f32, top: bool) -> f32 {
 }
 
 pub fn calc_frustum_x_border(frustum: &Frustum, z_in: f32, right: bool) -> f32 {
-	let z = z_in + 0.05; // FIXME: add z offset for glyphs as a camera parameter? maybe to text descriptor
+	let z = z_in + GLYPH_Z_OFFSET;
 
 	let plane_index = if right { FrustumPlane::Right } else { FrustumPlane::Left } as usize;
 
@@ -96,15 +112,14 @@ pub fn calc_frustum_x_border(frustum: &Frustum, z_in: f32, right: bool) -> f32 {
 	(-plane.w - plane.z * z) / plane.x // assume y = 0
 }
 
-pub fn calc_visible_rows(
+pub fn calc_visible_height(
 	frustum				: &Frustum,
 	target_z			: f32,
-	row_height			: f32,
 ) -> f32 {
 	let y_top			= calc_frustum_y_border(frustum, target_z, true);
 	let y_bottom		= calc_frustum_y_border(frustum, target_z, false);
 
-	(y_top - y_bottom) / row_height
+	y_top - y_bottom
 }
 
 pub fn calc_visible_columns(
@@ -118,22 +133,21 @@ pub fn calc_visible_columns(
 	(x_right - x_left) / column_width
 }
 
+/// Wheel deltas in lines (mouse wheel) and in pixels (touchpad), positive is towards the end of text
 pub fn delta_wheel_from_events(
-	pixels_per_line					: f32,
 	mut mouse_wheel_event_reader	: EventReader<MouseWheel>,
-) -> Option<f32> {
-	let scroll_event_occured = !mouse_wheel_event_reader.is_empty();
-	let mut wheel_scalar	= 0.0;
+) -> (Option<Vec2>, Option<Vec2>) {
+	let mut lines			= None;
+	let mut pixels			= None;
 	for event in mouse_wheel_event_reader.iter() {
-		// scale the event magnitude per pixel or per line
-		let scroll_amount = match event.unit {
-			MouseScrollUnit::Line => { event.y },
-			MouseScrollUnit::Pixel => { event.y / pixels_per_line },
+		let delta = match event.unit {
+			MouseScrollUnit::Line => { lines.get_or_insert(Vec2::ZERO) },
+			MouseScrollUnit::Pixel => { pixels.get_or_insert(Vec2::ZERO) },
 		};
-		wheel_scalar += -scroll_amount;
+		*delta += -Vec2::new(event.x, event.y);
 	}
 
-	if scroll_event_occured { Some(wheel_scalar) } else { None }
+	(lines, pixels)
 }
 
 pub fn delta_mouse_from_events(
@@ -145,4 +159,43 @@ pub fn delta_mouse_from_events(
 	}
 
 	delta_mouse
-}
\ No newline at end of file
+}
+
+/// Bottom left corner of camera viewport in window coordinates (logical pixels, bottom left origin like Window::cursor_position)
+pub fn viewport_origin(
+	window				: &Window,
+	camera				: &Camera,
+) -> Option<Vec2> {
+	let (min, max)		= camera.logical_viewport_rect()?;
+
+	// viewport rect has top left origin while window coordinates have bottom left
+	Some(Vec2::new(min.x, window.height() - max.y))
+}
+
+/// Cursor position inside camera viewport in logical pixels with bottom left origin (same as viewport_to_world expects), None if cursor is outside
+pub fn viewport_cursor_position(
+	window				: &Window,
+	camera				: &Camera,
+) -> Option<Vec2> {
+	let cursor			= window.cursor_position()?;
+	let position		= cursor - viewport_origin(window, camera)?;
+	let size			= camera.logical_viewport_size()?;
+
+	if position.cmplt(Vec2::ZERO).any() || position.cmpgt(size).any() {
+		return None
+	}
+
+	Some(position)
+}
+
+/// Cameras without custom viewport get pointer input regardless of where the cursor is
+pub fn camera_receives_pointer(
+	window				: Option<&Window>,
+	camera				: &Camera,
+) -> bool {
+	if camera.viewport.is_none() {
+		return true
+	}
+
+	window.and_then(|window| viewport_cursor_position(window, camera)).is_some()
+}
@@ -0,0 +1,48 @@
+use bevy :: prelude :: *;
+use bevy :: input :: { ButtonState, keyboard :: KeyboardInput };
+
+use bevy_reader_camera :: { * };
+
+mod common;
+use common :: Reader;
+
+fn key(reader: &mut Reader, key_code: KeyCode, state: ButtonState) {
+	reader.app.world.send_event(KeyboardInput { scan_code: 0, key_code: Some(key_code), state });
+}
+
+#[test]
+fn arrows_scroll_by_line_and_keep_scrolling_while_held() {
+	let mut reader = Reader::new();
+
+	key(&mut reader, KeyCode::Down, ButtonState::Pressed);
+	reader.step(1.0 / 60.0);
+	key(&mut reader, KeyCode::Down, ButtonState::Released);
+	reader.run(60.0, 2.0);
+	assert_eq!(reader.row_offset, 1);
+
+	key(&mut reader, KeyCode::Down, ButtonState::Pressed);
+	reader.run(60.0, 1.0);
+	key(&mut reader, KeyCode::Down, ButtonState::Released);
+	reader.run(60.0, 2.0);
+	assert!(reader.row_offset > 10);
+}
+
+#[test]
+fn held_only_binding_leaves_the_press_to_app() {
+	let mut reader = Reader::new();
+	reader.camera().actions
+		.unbind(CameraAction::ScrollLineDown)
+		.bind(CameraAction::ScrollLineDown, Binding::key(KeyCode::Down).held_only());
+
+	key(&mut reader, KeyCode::Down, ButtonState::Pressed);
+	reader.step(1.0 / 60.0);
+	key(&mut reader, KeyCode::Down, ButtonState::Released);
+	reader.run(60.0, 2.0);
+	assert_eq!(reader.row_offset, 0);
+
+	key(&mut reader, KeyCode::Down, ButtonState::Pressed);
+	reader.run(60.0, 1.0);
+	key(&mut reader, KeyCode::Down, ButtonState::Released);
+	reader.run(60.0, 2.0);
+	assert!(reader.row_offset > 10);
+}
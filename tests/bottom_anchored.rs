@@ -0,0 +1,81 @@
+use bevy :: prelude :: *;
+use bevy :: input :: { ButtonState, keyboard :: KeyboardInput, mouse :: { MouseScrollUnit, MouseWheel } };
+
+use bevy_reader_camera :: { * };
+
+mod common;
+use common :: { GLYPH_HEIGHT, Reader };
+
+fn reader() -> Reader {
+	let mut reader	= Reader::new();
+	let target		= reader.target();
+	reader.app.world.get_mut::<TextDescriptor>(target).unwrap().bottom_anchored = true;
+	reader.run(60.0, 2.0);
+
+	reader
+}
+
+/// Row at the bottom edge of viewport counted up from the origin
+fn bottom_row(reader: &mut Reader) -> f32 {
+	let half_height = reader.camera().visible_height / 2.0;
+	(reader.translation().y - half_height) / GLYPH_HEIGHT
+}
+
+#[test]
+fn starts_at_the_newest_row() {
+	let mut reader = reader();
+
+	assert!(reader.translation().y > 0.0);
+	assert!(bottom_row(&mut reader).abs() < 1e-3);
+	assert_eq!(reader.camera().row_offset_out(), 0);
+}
+
+#[test]
+fn row_offsets_are_counted_from_the_bottom() {
+	let mut reader = reader();
+
+	reader.command(CameraCommand::ScrollTo(40));
+	reader.run(60.0, 2.0);
+
+	assert_eq!(reader.row_offset, 40);
+	assert_eq!(reader.camera().row_offset_out(), 40);
+	assert!((bottom_row(&mut reader) - 40.0).abs() < 1e-3);
+}
+
+#[test]
+fn scrolling_up_the_screen_loads_history() {
+	let mut reader = reader();
+
+	reader.command(CameraCommand::PageUp);
+	reader.run(60.0, 2.0);
+	let row_offset = reader.row_offset;
+	assert!(row_offset > 0);
+
+	reader.app.world.send_event(KeyboardInput { scan_code: 0, key_code: Some(KeyCode::Up), state: ButtonState::Pressed });
+	reader.step(1.0 / 60.0);
+	reader.app.world.send_event(KeyboardInput { scan_code: 0, key_code: Some(KeyCode::Up), state: ButtonState::Released });
+	reader.run(60.0, 2.0);
+	assert_eq!(reader.row_offset, row_offset + 1);
+
+	// wheel down goes back towards the newest rows
+	for _ in 0..10 {
+		reader.app.world.send_event(MouseWheel { unit: MouseScrollUnit::Line, x: 0.0, y: -1.0 });
+		reader.step(1.0 / 60.0);
+	}
+	reader.run(60.0, 3.0);
+
+	assert!(reader.row_offset < row_offset);
+	assert!((bottom_row(&mut reader) - reader.row_offset as f32).abs() < 1e-3);
+}
+
+#[test]
+fn picking_goes_up_from_the_origin() {
+	let text		= TextDescriptor { glyph_width: 0.1, glyph_height: GLYPH_HEIGHT, rows: 100, columns: 80, bottom_anchored: true };
+	let transform	= Transform::default();
+
+	let world		= glyph_to_world(2.5, 3.5, &transform, &text, None);
+	assert!((world.y - 0.5).abs() < 1e-5);
+
+	let glyph		= world_to_glyph(world, &transform, &text, None);
+	assert!((glyph - Vec2::new(3.5, 2.5)).length() < 1e-5);
+}
@@ -0,0 +1,42 @@
+use bevy_reader_camera :: { * };
+
+mod common;
+use common :: Reader;
+
+#[test]
+fn caret_is_kept_within_scrolloff() {
+	let mut reader		= Reader::at(100);
+	let fully_visible	= reader.camera().visible_rows.floor() as u32;
+	let scrolloff		= reader.camera().scrolloff_bottom;
+
+	// moving inside of viewport doesn't scroll
+	reader.camera().set_caret(105, 60);
+	reader.run(60.0, 1.0);
+	assert_eq!(reader.row_offset, 100);
+
+	// below the viewport: caret ends up scrolloff rows above the bottom edge
+	let caret			= 100 + fully_visible + 10;
+	reader.camera().set_caret(caret, 60);
+	reader.run(60.0, 2.0);
+	assert_eq!(reader.row_offset, caret + scrolloff + 1 - fully_visible);
+
+	// above the viewport: scrolloff rows below the top edge
+	let scrolloff		= reader.camera().scrolloff_top;
+	reader.camera().set_caret(50, 60);
+	reader.run(60.0, 2.0);
+	assert_eq!(reader.row_offset, 50 - scrolloff);
+}
+
+#[test]
+fn user_can_scroll_away_from_caret() {
+	let mut reader = Reader::at(100);
+
+	reader.camera().set_caret(105, 60);
+	reader.run(60.0, 1.0);
+
+	reader.command(CameraCommand::ScrollBy(200));
+	reader.run(60.0, 2.0);
+
+	// caret didn't move so camera stays where user scrolled
+	assert_eq!(reader.row_offset, 300);
+}
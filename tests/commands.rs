@@ -0,0 +1,38 @@
+use bevy_reader_camera :: { * };
+
+mod common;
+use common :: Reader;
+
+#[test]
+fn scroll_commands_move_row_offset() {
+	let mut reader = Reader::new();
+
+	reader.command(CameraCommand::ScrollTo(50));
+	reader.run(60.0, 2.0);
+	assert_eq!(reader.row_offset, 50);
+	assert_eq!(reader.camera().row_offset_out(), 50);
+
+	reader.command(CameraCommand::ScrollBy(-20));
+	reader.run(60.0, 2.0);
+	assert_eq!(reader.row_offset, 30);
+	assert_eq!(reader.camera().row_offset_out(), 30);
+}
+
+#[test]
+fn zoom_column_and_mode_commands() {
+	let mut reader = Reader::new();
+
+	reader.command(CameraCommand::SetZoom(12.0));
+	reader.command(CameraCommand::SetColumn(70));
+	reader.run(60.0, 3.0);
+
+	assert!((reader.camera().zoom - 12.0).abs() < 1e-2);
+	assert!((reader.translation().z - 12.0).abs() < 1e-2);
+	// column stays where it was asked to be instead of going back to the center
+	assert_eq!(reader.camera().column, 70);
+	assert!((reader.translation().x - 70.0 * 0.1).abs() < 1e-3);
+
+	reader.command(CameraCommand::SetMode(CameraMode::Fly));
+	reader.step(1.0 / 60.0);
+	assert_eq!(reader.camera().mode, CameraMode::Fly);
+}
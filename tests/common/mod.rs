@@ -0,0 +1,205 @@
+// every test file uses its own part of the harness
+#![allow(dead_code)]
+
+use std :: time :: { Duration, Instant };
+
+use bevy :: prelude :: *;
+use bevy :: input :: { InputPlugin, touch :: { TouchInput, TouchPhase } };
+use bevy :: window :: { PrimaryWindow, WindowCreated, WindowResized };
+use bevy :: render :: camera :: camera_system;
+use bevy :: ecs :: event :: ManualEventReader;
+
+use bevy_reader_camera :: { * };
+
+/// Height of rows in text every Reader starts with
+pub const GLYPH_HEIGHT: f32 = 0.2;
+
+// Reader camera driven through a full app with manually advanced time,
+// test plays the role of an app that scrolls its text right away
+
+pub struct Reader {
+	pub app			: App,
+	pub camera		: Entity,
+	time			: Instant,
+	requests		: ManualEventReader<RowScrollRequest>,
+	pub row_offset	: u32,
+	/// answer requests right away, otherwise they are summed up in requested until ack
+	pub auto_ack	: bool,
+	pub requested	: i32,
+}
+
+impl Default for Reader {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Reader {
+	pub fn new() -> Self {
+		Self::with_projection(Projection::default())
+	}
+
+	/// Reader scrolled to given row with camera settled there
+	pub fn at(row: u32) -> Self {
+		let mut reader = Self::new();
+		reader.command(CameraCommand::ScrollTo(row));
+		reader.run(60.0, 2.0);
+
+		reader
+	}
+
+	pub fn with_projection(projection: Projection) -> Self {
+		let mut app = App::new();
+
+		app
+			.add_plugin(InputPlugin)
+			.add_plugin(ReaderCameraPlugin)
+			.init_resource::<Time>()
+		;
+
+		let target = app.world.spawn((
+			Transform::default(),
+			TextDescriptor { glyph_width: 0.1, glyph_height: GLYPH_HEIGHT, rows: 10_000, columns: 120, ..Default::default() },
+		)).id();
+
+		let mut reader_camera			= ReaderCamera::default();
+		reader_camera.mode				= CameraMode::Reader;
+		reader_camera.target_entity		= Some(target);
+		reader_camera.jump_mode			= JumpMode::Instant;
+
+		let camera = app.world.spawn((
+			Camera3dBundle { transform: Transform::from_xyz(0.0, 0.0, 7.0), projection, ..default() },
+			reader_camera,
+		)).id();
+
+		let time = Instant::now();
+		app.world.resource_mut::<Time>().update_with_instant(time);
+
+		let mut reader = Self { app, camera, time, requests: ManualEventReader::default(), row_offset: 0, auto_ack: true, requested: 0 };
+
+		// let camera settle at the same frame rate for every run
+		reader.run(60.0, 2.0);
+
+		reader
+	}
+
+	/// Headless app has no renderer, so camera gets its viewport size and projection matrix from a window directly.
+	/// Window is 800 x 600 and camera is scrolled to row 100 to have text above and below
+	pub fn with_window() -> Self {
+		let mut reader = Self::new();
+
+		reader.app
+			.add_plugin(AssetPlugin::default())
+			.add_asset::<Image>()
+			.add_event::<WindowCreated>()
+			.add_event::<WindowResized>()
+			.add_system(camera_system::<Projection>.in_base_set(CoreSet::PostUpdate))
+		;
+
+		reader.app.world.spawn((Window { resolution: (800.0, 600.0).into(), ..default() }, PrimaryWindow));
+
+		reader.command(CameraCommand::ScrollTo(100));
+		reader.run(60.0, 2.0);
+
+		reader
+	}
+
+	pub fn step(&mut self, delta_seconds: f32) {
+		self.time += Duration::from_secs_f32(delta_seconds);
+		self.app.world.resource_mut::<Time>().update_with_instant(self.time);
+
+		self.app.update();
+
+		let events = self.app.world.resource::<Events<RowScrollRequest>>();
+		let rows: i32 = self.requests.iter(events).map(|request| request.rows).sum();
+		if !self.auto_ack {
+			self.requested += rows;
+		} else if rows != 0 {
+			self.row_offset = (self.row_offset as i32 + rows).max(0) as u32;
+			let ack = RowOffsetAck { camera: self.camera, row_offset: self.row_offset };
+			self.app.world.send_event(ack);
+		}
+	}
+
+	/// Answers every request received so far with given row offset, app may clamp what was requested
+	pub fn ack(&mut self, row_offset: u32) {
+		self.row_offset	= row_offset;
+		self.requested	= 0;
+		self.app.world.send_event(RowOffsetAck { camera: self.camera, row_offset });
+	}
+
+	pub fn run(&mut self, fps: f32, seconds: f32) {
+		let frames = (seconds * fps).round() as usize;
+		for _ in 0..frames {
+			self.step(1.0 / fps);
+		}
+	}
+
+	pub fn command(&mut self, command: CameraCommand) {
+		self.app.world.send_event(ReaderCameraCommand { camera: self.camera, command });
+	}
+
+	/// Finger position in logical pixels with top left origin like touch screens report it
+	pub fn touch(&mut self, id: u64, phase: TouchPhase, position: Vec2) {
+		self.app.world.send_event(TouchInput { phase, position, force: None, id });
+	}
+
+	/// Moves fingers from one set of positions to another over given amount of frames and lifts them
+	pub fn gesture(&mut self, from: &[Vec2], to: &[Vec2], frames: usize) {
+		for (id, position) in from.iter().enumerate() {
+			self.touch(id as u64, TouchPhase::Started, *position);
+		}
+		self.step(1.0 / 60.0);
+
+		for frame in 1..=frames {
+			let t = frame as f32 / frames as f32;
+			for (id, (from, to)) in from.iter().zip(to).enumerate() {
+				self.touch(id as u64, TouchPhase::Moved, from.lerp(*to, t));
+			}
+			self.step(1.0 / 60.0);
+		}
+
+		for (id, position) in to.iter().enumerate() {
+			self.touch(id as u64, TouchPhase::Ended, *position);
+		}
+		self.step(1.0 / 60.0);
+	}
+
+	/// Edits text like an app would: changes the amount of rows, shifts own row offset and tells the camera
+	pub fn edit_rows(&mut self, row: u32, removed: u32, inserted: u32) {
+		let target	= self.target();
+		let edit	= RowsEdited { target, row, removed, inserted };
+
+		let mut text_descriptor = self.app.world.get_mut::<TextDescriptor>(target).unwrap();
+		text_descriptor.rows = text_descriptor.rows - removed as usize + inserted as usize;
+
+		self.row_offset = edit.anchored(self.row_offset);
+		self.app.world.send_event(edit);
+	}
+
+	pub fn target(&mut self) -> Entity {
+		self.camera().target_entity.unwrap()
+	}
+
+	pub fn camera(&mut self) -> Mut<'_, ReaderCamera> {
+		self.app.world.get_mut::<ReaderCamera>(self.camera).unwrap()
+	}
+
+	/// Visual row at the top edge of viewport
+	pub fn top_row(&mut self) -> f32 {
+		let half_height = self.camera().visible_height / 2.0;
+		(-self.translation().y - half_height) / GLYPH_HEIGHT
+	}
+
+	pub fn translation(&self) -> Vec3 {
+		self.app.world.get::<Transform>(self.camera).unwrap().translation
+	}
+
+	pub fn projection(&self) -> &Projection {
+		self.app.world.get::<Projection>(self.camera).unwrap()
+	}
+
+	pub fn pitch(&self) -> f32 {
+		self.app.world.get::<ReaderCamera>(self.camera).unwrap().pitch
+	}
+}
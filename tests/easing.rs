@@ -0,0 +1,199 @@
+use bevy :: prelude :: *;
+
+use bevy_reader_camera :: { * };
+
+mod common;
+use common :: Reader;
+
+// the same motion sampled at different frame rates has to end up in the same place at the same moments
+
+const SECONDS: f32 = 1.5;
+// common multiple of every tested frame time so all runs are sampled at the same moments
+const SAMPLE_SECONDS: f32 = 1.0 / 15.0;
+
+/// Camera positions at every sample moment after sending given command
+fn trajectory(fps: f32, setup: impl Fn(&mut Reader)) -> Vec<Vec3> {
+	let mut reader = Reader::new();
+
+	setup(&mut reader);
+
+	let samples = (SECONDS / SAMPLE_SECONDS).round() as usize;
+	(0..samples).map(|_| {
+		reader.run(fps, SAMPLE_SECONDS);
+		reader.translation()
+	}).collect()
+}
+
+fn assert_same_trajectory(setup: impl Fn(&mut Reader) + Copy, tolerance: f32) {
+	let reference = trajectory(60.0, setup);
+
+	assert!(reference.first() != reference.last(), "camera didn't move");
+
+	for fps in [30.0, 165.0] {
+		for (sample, (expected, actual)) in reference.iter().zip(trajectory(fps, setup)).enumerate() {
+			assert!(
+				expected.distance(actual) <= tolerance,
+				"{fps} fps differs from 60 fps at {:.3}s: {expected} vs {actual}",
+				(sample + 1) as f32 * SAMPLE_SECONDS,
+			);
+		}
+	}
+}
+
+#[test]
+fn ease_factor_composes() {
+	let easing_seconds	= 0.1;
+	let once			= ease(0.0, 1.0, 0.1, easing_seconds);
+
+	let mut steps		= 0.0;
+	for _ in 0..10 {
+		steps			= ease(steps, 1.0, 0.01, easing_seconds);
+	}
+
+	assert!((once - steps).abs() < 1e-5);
+	assert!((once - (1.0 - (-1.0f32).exp())).abs() < 1e-5);
+}
+
+#[test]
+fn ease_factor_is_instant_without_easing() {
+	assert_eq!(ease_factor(0.016, 0.0), 1.0);
+	assert_eq!(ease(3.0, 5.0, 0.016, 0.0), 5.0);
+}
+
+#[test]
+fn ease_factor_never_overshoots() {
+	// a frame way longer than easing still lands on target, no matter how long the app slept
+	let value = ease(0.0, 1.0, 100.0, 0.01);
+	assert!(value <= 1.0 && value > 0.999);
+}
+
+#[test]
+fn zoom_is_frame_rate_independent() {
+	// camera eases towards zoom that is being eased itself and chasing a moving target can't match exactly
+	// between frame rates, so we allow a few percent of 13 units of travel
+	assert_same_trajectory(|reader| reader.command(CameraCommand::SetZoom(20.0)), 1.0);
+}
+
+#[test]
+fn scroll_is_frame_rate_independent() {
+	assert_same_trajectory(|reader| reader.command(CameraCommand::ScrollBy(20)), 0.001);
+}
+
+#[test]
+fn dormant_camera_is_frame_rate_independent() {
+	let setup = |reader: &mut Reader| {
+		reader.camera().put_to_sleep();
+		reader.command(CameraCommand::ScrollBy(20));
+	};
+
+	assert_same_trajectory(setup, 0.001);
+}
+
+/// Value of given easing after running for given time at given frame rate
+fn eased(easing: Easing, fps: f32, seconds: f32) -> f32 {
+	let mut state	= EasingState::default();
+	let mut value	= 0.0;
+	for _ in 0..(seconds * fps).round() as usize {
+		value		= easing.step(value, 1.0, 1.0 / fps, &mut state);
+	}
+	value
+}
+
+#[test]
+fn fixed_duration_curves_end_in_time() {
+	for easing in [Easing::Linear { seconds: 0.3 }, Easing::Cubic { seconds: 0.3 }, Easing::EaseInOut { seconds: 0.3 }] {
+		assert!(eased(easing, 60.0, 0.2) < 1.0, "{easing:?} ended too early");
+		assert_eq!(eased(easing, 60.0, 0.3), 1.0, "{easing:?} didn't end in time");
+	}
+}
+
+#[test]
+fn fixed_duration_curves_are_frame_rate_independent() {
+	for easing in [Easing::Linear { seconds: 0.3 }, Easing::Cubic { seconds: 0.3 }, Easing::EaseInOut { seconds: 0.3 }] {
+		for seconds in [SAMPLE_SECONDS * 2.0, SAMPLE_SECONDS * 4.0] {
+			let expected = eased(easing, 60.0, seconds);
+			for fps in [30.0, 165.0] {
+				assert!((eased(easing, fps, seconds) - expected).abs() < 1e-4, "{easing:?} at {fps} fps");
+			}
+		}
+	}
+}
+
+#[test]
+fn linear_is_linear_in_time() {
+	assert!((eased(Easing::Linear { seconds: 0.4 }, 60.0, 0.1) - 0.25).abs() < 1e-4);
+}
+
+#[test]
+fn fixed_duration_curves_follow_moving_target() {
+	let easing		= Easing::EaseInOut { seconds: 0.3 };
+	let mut state	= EasingState::default();
+	let mut value	= 0.0;
+	for frame in 0..18 {
+		let target	= 1.0 + frame as f32 * 0.1;
+		value		= easing.step(value, target, 1.0 / 60.0, &mut state);
+	}
+
+	// target kept moving but the animation still ended at 0.3s
+	assert_eq!(value, 1.0 + 17.0 * 0.1);
+}
+
+#[test]
+fn critically_damped_spring_does_not_overshoot() {
+	let easing		= Easing::Spring { stiffness: 200.0, damping: 1.0 };
+	let mut state	= EasingState::default();
+	let mut value	= 0.0;
+	for _ in 0..120 {
+		value		= easing.step(value, 1.0, 1.0 / 60.0, &mut state);
+		assert!(value <= 1.0);
+	}
+
+	assert_eq!(value, 1.0);
+}
+
+#[test]
+fn underdamped_spring_bounces() {
+	let easing		= Easing::Spring { stiffness: 200.0, damping: 0.3 };
+	let mut state	= EasingState::default();
+	let mut value	= 0.0;
+	let mut peak	= 0.0f32;
+	for _ in 0..60 {
+		value		= easing.step(value, 1.0, 1.0 / 60.0, &mut state);
+		peak		= peak.max(value);
+	}
+
+	assert!(peak > 1.1);
+}
+
+#[test]
+fn spring_is_frame_rate_independent() {
+	let easing = Easing::Spring { stiffness: 200.0, damping: 0.5 };
+	for seconds in [SAMPLE_SECONDS * 2.0, SAMPLE_SECONDS * 4.0, SAMPLE_SECONDS * 6.0] {
+		let expected = eased(easing, 240.0, seconds);
+		for fps in [30.0, 60.0, 165.0] {
+			assert!((eased(easing, fps, seconds) - expected).abs() < 0.01, "{fps} fps at {seconds}s");
+		}
+	}
+}
+
+#[test]
+fn spring_rotation_settles() {
+	let easing		= Easing::Spring { stiffness: 200.0, damping: 1.0 };
+	let mut state	= EasingState::default();
+	let target		= Quat::from_rotation_x(1.0);
+	let mut value	= Quat::IDENTITY;
+	for _ in 0..120 {
+		value		= easing.step(value, target, 1.0 / 60.0, &mut state);
+	}
+
+	assert!(value.angle_between(target) < 1e-3);
+}
+
+#[test]
+fn blend_interpolates_same_kind() {
+	let blended = Easing::Exponential { seconds: 0.1 }.blend(&Easing::Exponential { seconds: 0.3 }, 0.5);
+	assert!((blended.seconds() - 0.2).abs() < 1e-6);
+
+	let mixed = Easing::Linear { seconds: 0.1 }.blend(&Easing::Spring { stiffness: 100.0, damping: 1.0 }, 0.7);
+	assert!(matches!(mixed, Easing::Spring { .. }));
+}
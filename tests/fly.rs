@@ -0,0 +1,143 @@
+use bevy :: prelude :: *;
+use bevy :: input :: mouse :: { MouseWheel, MouseScrollUnit };
+
+use bevy_reader_camera :: { * };
+
+mod common;
+use common :: Reader;
+
+fn flying() -> Reader {
+	let mut reader = Reader::new();
+
+	reader.camera().enabled_translation = true;
+	reader.command(CameraCommand::SetMode(CameraMode::Fly));
+	// let mode transition finish
+	reader.run(60.0, 1.0);
+
+	reader
+}
+
+fn press(reader: &mut Reader, key: KeyCode) {
+	reader.app.world.resource_mut::<Input<KeyCode>>().press(key);
+}
+
+fn release(reader: &mut Reader, key: KeyCode) {
+	reader.app.world.resource_mut::<Input<KeyCode>>().release(key);
+}
+
+fn speed(reader: &mut Reader) -> f32 {
+	reader.camera().velocity.length()
+}
+
+#[test]
+fn speed_is_capped_by_max_speed() {
+	let mut reader = flying();
+	reader.camera().drag = 0.0;
+
+	press(&mut reader, KeyCode::W);
+	reader.run(60.0, 2.0);
+
+	let max_speed = reader.camera().max_speed;
+	assert!((speed(&mut reader) - max_speed).abs() < 1e-3);
+}
+
+#[test]
+fn camera_accelerates_and_coasts_to_stop() {
+	let mut reader = flying();
+	let start = reader.translation();
+
+	press(&mut reader, KeyCode::W);
+	reader.step(1.0 / 60.0);
+	let first_frame = speed(&mut reader);
+	reader.run(60.0, 0.5);
+
+	// speed builds up instead of jumping to its limit
+	assert!(first_frame > 0.0 && first_frame < speed(&mut reader));
+
+	release(&mut reader, KeyCode::W);
+	reader.step(1.0 / 60.0);
+	let moving = reader.translation();
+
+	// camera keeps moving for a while after key is released
+	assert!(speed(&mut reader) > 0.0);
+	assert!(moving.z < start.z);
+
+	reader.run(60.0, 5.0);
+	assert!(speed(&mut reader) < 1e-3);
+	assert!(reader.translation().z < moving.z);
+}
+
+#[test]
+fn sprint_flies_faster() {
+	let mut walk = flying();
+	press(&mut walk, KeyCode::W);
+	walk.run(60.0, 3.0);
+
+	let mut sprint = flying();
+	press(&mut sprint, KeyCode::W);
+	press(&mut sprint, KeyCode::LControl);
+	sprint.run(60.0, 3.0);
+
+	let multiplier = sprint.camera().sprint_multiplier;
+	assert!(speed(&mut walk) > 0.0);
+	assert!((speed(&mut sprint) - speed(&mut walk) * multiplier).abs() < 1e-2);
+}
+
+#[test]
+fn wheel_changes_speed() {
+	let mut reader = flying();
+
+	reader.app.world.send_event(MouseWheel { unit: MouseScrollUnit::Line, x: 0.0, y: 3.0 });
+	reader.step(1.0 / 60.0);
+	let faster = reader.camera().speed_scale;
+
+	reader.app.world.send_event(MouseWheel { unit: MouseScrollUnit::Line, x: 0.0, y: -6.0 });
+	reader.step(1.0 / 60.0);
+	let slower = reader.camera().speed_scale;
+
+	assert!(faster > 1.0 && slower < 1.0);
+}
+
+#[test]
+fn flying_is_frame_rate_independent() {
+	let distance = |fps: f32| {
+		let mut reader = flying();
+		let start = reader.translation();
+
+		press(&mut reader, KeyCode::W);
+		reader.run(fps, 0.4);
+		release(&mut reader, KeyCode::W);
+		reader.run(fps, 2.0);
+
+		reader.translation().distance(start)
+	};
+
+	let expected = distance(60.0);
+	for fps in [30.0, 165.0] {
+		// velocity is integrated exactly, position is stepped so a few percent are allowed
+		assert!((distance(fps) - expected).abs() < expected * 0.05, "{fps} fps");
+	}
+}
+
+#[test]
+fn mouse_goes_only_to_camera_under_cursor() {
+	use bevy :: input :: mouse :: MouseMotion;
+	use bevy :: render :: camera :: Viewport;
+
+	let yaw_after_mouse = |viewport: Option<Viewport>| {
+		let mut reader = flying();
+		reader.camera().enabled_rotation = true;
+		reader.app.world.get_mut::<Camera>(reader.camera).unwrap().viewport = viewport;
+
+		let yaw = reader.camera().yaw;
+		reader.app.world.send_event(MouseMotion { delta: Vec2::new(50.0, 0.0) });
+		reader.step(1.0 / 60.0);
+
+		reader.camera().yaw - yaw
+	};
+
+	assert!(yaw_after_mouse(None).abs() > 0.0);
+	// no window so cursor is never inside of custom viewport
+	let viewport = Viewport { physical_size: UVec2::new(100, 100), ..default() };
+	assert_eq!(yaw_after_mouse(Some(viewport)), 0.0);
+}
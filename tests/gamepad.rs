@@ -0,0 +1,70 @@
+use bevy :: prelude :: *;
+use bevy :: input :: gamepad :: { GamepadAxisChangedEvent, GamepadConnection, GamepadConnectionEvent, GamepadEvent, GamepadInfo };
+
+use bevy_reader_camera :: { * };
+
+mod common;
+use common :: Reader;
+
+const GAMEPAD: Gamepad = Gamepad { id: 0 };
+
+fn with_gamepad() -> Reader {
+	let mut reader = Reader::new();
+
+	let connection = GamepadConnectionEvent::new(GAMEPAD, GamepadConnection::Connected(GamepadInfo { name: "test".into() }));
+	reader.app.world.send_event(GamepadEvent::from(connection));
+	reader.step(1.0 / 60.0);
+
+	reader
+}
+
+fn left_stick_y(reader: &mut Reader, value: f32) {
+	let axis = GamepadAxisChangedEvent::new(GAMEPAD, GamepadAxisType::LeftStickY, value);
+	reader.app.world.send_event(GamepadEvent::from(axis));
+}
+
+#[test]
+fn deadzone_rescales_the_rest_of_axis() {
+	let tuning = GamepadAxisTuning { deadzone: 0.2, invert: false };
+
+	assert_eq!(tuning.apply(0.1), 0.0);
+	assert_eq!(tuning.apply(-0.2), 0.0);
+	assert!((tuning.apply(0.6) - 0.5).abs() < 1e-5);
+	assert!((tuning.apply(-1.0) + 1.0).abs() < 1e-5);
+
+	let inverted = GamepadAxisTuning { invert: true, ..tuning };
+	assert!((inverted.apply(0.6) + 0.5).abs() < 1e-5);
+	assert_eq!(inverted.apply(0.1), 0.0);
+}
+
+#[test]
+fn stick_inside_deadzone_does_not_scroll() {
+	let mut reader = with_gamepad();
+
+	left_stick_y(&mut reader, -0.1);
+	reader.run(60.0, 1.0);
+	assert_eq!(reader.row_offset, 0);
+
+	// down the screen is towards the end of text
+	left_stick_y(&mut reader, -0.6);
+	reader.run(60.0, 1.0);
+	assert!(reader.row_offset > 0);
+}
+
+#[test]
+fn deadzones_are_per_axis() {
+	let mut reader = with_gamepad();
+	reader.camera().gamepad.left_y.deadzone = 0.7;
+
+	left_stick_y(&mut reader, -0.6);
+	reader.run(60.0, 1.0);
+	assert_eq!(reader.row_offset, 0);
+
+	// deadzone of another axis changes nothing
+	let mut reader = with_gamepad();
+	reader.camera().gamepad.left_x.deadzone = 0.7;
+
+	left_stick_y(&mut reader, -0.6);
+	reader.run(60.0, 1.0);
+	assert!(reader.row_offset > 0);
+}
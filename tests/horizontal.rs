@@ -0,0 +1,51 @@
+use bevy :: prelude :: *;
+use bevy :: input :: mouse :: MouseMotion;
+
+use bevy_reader_camera :: { * };
+
+mod common;
+use common :: Reader;
+
+#[test]
+fn starts_at_the_center_of_text() {
+	let mut reader = Reader::new();
+
+	assert_eq!(reader.camera().column, 60);
+	assert!((reader.translation().x - 60.0 * 0.1).abs() < 1e-3);
+
+	// centering happens only once, column set by app stays
+	reader.camera().column = 70;
+	reader.run(60.0, 1.0);
+	assert_eq!(reader.camera().column, 70);
+}
+
+#[test]
+fn column_is_clamped_to_text_width() {
+	let mut reader		= Reader::new();
+	let visible_half	= reader.camera().visible_columns / 2.0;
+
+	reader.command(CameraCommand::SetColumn(0));
+	reader.run(60.0, 1.0);
+	assert_eq!(reader.camera().column, visible_half.ceil() as usize);
+
+	reader.command(CameraCommand::SetColumn(1000));
+	reader.run(60.0, 1.0);
+	assert_eq!(reader.camera().column, (120.0 - visible_half).floor() as usize);
+}
+
+#[test]
+fn mouse_swipe_scrolls_columns_and_snaps() {
+	let mut reader = Reader::new();
+	reader.camera().enabled_translation = true;
+
+	let column = reader.camera().column;
+	for _ in 0..10 {
+		reader.app.world.send_event(MouseMotion { delta: Vec2::new(20.0, 0.0) });
+		reader.step(1.0 / 60.0);
+	}
+	reader.run(60.0, 3.0);
+
+	assert!(reader.camera().column > column);
+	// came to rest exactly on a column
+	assert!((reader.translation().x - reader.camera().column as f32 * 0.1).abs() < 1e-3);
+}
@@ -0,0 +1,64 @@
+use bevy_reader_camera :: { * };
+
+mod common;
+use common :: Reader;
+
+fn reader(jump_mode: JumpMode) -> Reader {
+	let mut reader = Reader::new();
+	reader.camera().jump_mode = jump_mode;
+
+	reader
+}
+
+#[test]
+fn long_jumps_fly_over_text() {
+	let mut reader	= reader(JumpMode::FlyOver);
+	let z			= reader.translation().z;
+
+	reader.command(CameraCommand::ScrollTo(5000));
+
+	let mut z_max	= z;
+	let mut frames	= 0;
+	loop {
+		reader.step(1.0 / 60.0);
+		z_max = z_max.max(reader.translation().z);
+		frames += 1;
+
+		if frames > 2 && !reader.camera().is_jumping() {
+			break
+		}
+		assert!(frames <= 60, "jump takes no longer than jump_max_seconds");
+	}
+
+	// zooms out halfway there and comes back to reading distance at target
+	assert!(z_max > z + 1.0);
+	assert!((reader.translation().z - z).abs() < 1e-3);
+	assert!((reader.translation().y - reader.camera().target_translation.y).abs() < 1e-3);
+}
+
+#[test]
+fn instant_jumps_snap_to_target() {
+	let mut reader	= reader(JumpMode::Instant);
+	let z			= reader.translation().z;
+
+	reader.command(CameraCommand::ScrollTo(5000));
+	reader.step(1.0 / 60.0);
+	reader.step(1.0 / 60.0);
+
+	assert!(!reader.camera().is_jumping());
+	assert!((reader.translation().z - z).abs() < 1e-3);
+	assert!((reader.translation().y - reader.camera().target_translation.y).abs() < 1e-3);
+}
+
+#[test]
+fn short_scrolls_ease_without_jumping() {
+	let mut reader	= reader(JumpMode::FlyOver);
+	let z			= reader.translation().z;
+
+	reader.command(CameraCommand::ScrollBy(5));
+	for _ in 0..30 {
+		reader.step(1.0 / 60.0);
+		assert!(!reader.camera().is_jumping());
+		assert!(reader.translation().z <= z + 1e-4);
+	}
+}
@@ -0,0 +1,86 @@
+use bevy :: prelude :: *;
+use bevy :: ecs :: event :: ManualEventReader;
+
+use bevy_reader_camera :: { * };
+
+mod common;
+use common :: Reader;
+
+fn finished(reader: &Reader, events: &mut ManualEventReader<ModeTransitionFinished>) -> Vec<CameraMode> {
+	events.iter(reader.app.world.resource::<Events<ModeTransitionFinished>>()).map(|event| event.mode).collect()
+}
+
+#[test]
+fn reader_to_fly_keeps_camera_in_place() {
+	let mut reader	= Reader::new();
+	let mut events	= ManualEventReader::default();
+
+	let before		= reader.translation();
+
+	reader.camera().set_mode(CameraMode::Fly);
+	reader.step(1.0 / 60.0);
+
+	assert!(reader.translation().distance(before) < 1e-4);
+	assert!(reader.camera().target_translation.distance(before) < 1e-4);
+
+	// events live for two frames only so we collect them on the way
+	let mut modes	= Vec::new();
+	for _ in 0..60 {
+		reader.step(1.0 / 60.0);
+		modes.extend(finished(&reader, &mut events));
+	}
+
+	assert!(reader.translation().distance(before) < 1e-4);
+	assert_eq!(modes, vec![CameraMode::Fly]);
+}
+
+#[test]
+fn fly_to_reader_blends_over_transition() {
+	let mut reader	= Reader::new();
+	let mut events	= ManualEventReader::default();
+
+	let reader_translation = reader.translation();
+
+	reader.camera().set_mode(CameraMode::Fly);
+	reader.run(60.0, 1.0);
+
+	// fly away, then come back to reading
+	let away		= reader_translation + Vec3::new(30.0, 10.0, 40.0);
+	reader.app.world.get_mut::<Transform>(reader.camera).unwrap().translation = away;
+	reader.camera().target_translation = away;
+	reader.step(1.0 / 60.0);
+	finished(&reader, &mut events);
+
+	let seconds		= reader.camera().mode_transition_seconds;
+	reader.camera().set_mode(CameraMode::Reader);
+
+	let mut previous = reader.translation();
+	let mut largest_step = 0.0f32;
+	let mut modes	= Vec::new();
+	for _ in 0..(seconds * 60.0).round() as usize {
+		reader.step(1.0 / 60.0);
+		modes.extend(finished(&reader, &mut events));
+
+		assert!(!reader.camera().is_jumping());
+		largest_step = largest_step.max(reader.translation().distance(previous));
+		previous	= reader.translation();
+	}
+
+	// no snapping on the way
+	assert!(largest_step < away.distance(reader_translation) / 10.0);
+	assert!(reader.translation().distance(reader_translation) < 1e-3);
+	assert_eq!(modes, vec![CameraMode::Reader]);
+}
+
+#[test]
+fn switching_back_before_frame_end_does_nothing() {
+	let mut reader	= Reader::new();
+	let mut events	= ManualEventReader::default();
+
+	reader.camera().set_mode(CameraMode::Fly);
+	reader.camera().set_mode(CameraMode::Reader);
+	reader.step(1.0 / 60.0);
+
+	assert!(!reader.camera().is_mode_transitioning());
+	assert!(finished(&reader, &mut events).is_empty());
+}
@@ -0,0 +1,69 @@
+use bevy :: prelude :: *;
+use bevy :: input :: touch :: TouchPhase;
+use bevy :: input :: mouse :: { MouseScrollUnit, MouseWheel };
+
+mod common;
+use common :: Reader;
+
+fn with_momentum(momentum: bool) -> Reader {
+	let mut reader = Reader::with_window();
+	reader.camera().momentum.enabled = momentum;
+
+	reader
+}
+
+// quick swipe up the screen
+fn flick(reader: &mut Reader) {
+	reader.gesture(&[Vec2::new(400.0, 450.0)], &[Vec2::new(400.0, 250.0)], 4);
+}
+
+#[test]
+fn flick_keeps_scrolling_after_release_and_stops() {
+	let mut with	= with_momentum(true);
+	let mut without	= with_momentum(false);
+
+	flick(&mut with);
+	flick(&mut without);
+	let released	= with.row_offset;
+
+	with.run(60.0, 4.0);
+	without.run(60.0, 4.0);
+
+	assert!(with.row_offset > released);
+	assert!(with.row_offset > without.row_offset + 10);
+
+	// friction stops it eventually
+	let stopped		= with.row_offset;
+	with.run(60.0, 1.0);
+	assert_eq!(with.row_offset, stopped);
+}
+
+#[test]
+fn touch_and_wheel_stop_momentum() {
+	let mut reader	= with_momentum(true);
+	let mut free	= with_momentum(true);
+
+	// finger put down and held still
+	flick(&mut reader);
+	flick(&mut free);
+	reader.run(60.0, 0.1);
+	free.run(60.0, 0.1);
+	reader.touch(0, TouchPhase::Started, Vec2::new(400.0, 300.0));
+	reader.run(60.0, 3.0);
+
+	free.run(60.0, 3.0);
+	assert!(reader.row_offset + 10 < free.row_offset);
+
+	let held		= reader.row_offset;
+	reader.run(60.0, 1.0);
+	assert_eq!(reader.row_offset, held);
+
+	// wheel towards the start of text goes its own way right away
+	let mut reader	= with_momentum(true);
+	flick(&mut reader);
+	let released	= reader.row_offset;
+	reader.app.world.send_event(MouseWheel { unit: MouseScrollUnit::Line, x: 0.0, y: 1.0 });
+	reader.run(60.0, 3.0);
+
+	assert!(reader.row_offset < released);
+}
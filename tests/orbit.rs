@@ -0,0 +1,165 @@
+use bevy :: prelude :: *;
+use bevy :: input :: mouse :: { MouseMotion, MouseWheel, MouseScrollUnit };
+use bevy :: input :: gamepad :: { GamepadButtonChangedEvent, GamepadConnection, GamepadConnectionEvent, GamepadEvent, GamepadInfo };
+
+use bevy_reader_camera :: { * };
+
+mod common;
+use common :: Reader;
+
+fn orbit() -> Reader {
+	let mut reader = Reader::new();
+
+	let mut camera = reader.camera();
+	camera.set_mode(CameraMode::Orbit);
+	camera.set_restrictions(true, true, true, true);
+	camera.mode_transition_seconds = 0.0;
+
+	reader.step(1.0 / 60.0);
+	reader
+}
+
+fn pitch(reader: &Reader) -> f32 {
+	let forward = reader.app.world.get::<Transform>(reader.camera).unwrap().forward();
+	forward.y.asin().to_degrees()
+}
+
+#[test]
+fn switching_to_orbit_keeps_camera_in_place() {
+	let mut reader	= Reader::new();
+	let before		= *reader.app.world.get::<Transform>(reader.camera).unwrap();
+
+	reader.camera().set_mode(CameraMode::Orbit);
+	reader.run(60.0, 1.0);
+
+	let after		= reader.app.world.get::<Transform>(reader.camera).unwrap();
+	assert!(after.translation.distance(before.translation) < 1e-3);
+	assert!(after.rotation.angle_between(before.rotation) < 1e-3);
+}
+
+#[test]
+fn pitch_is_limited() {
+	let mut reader = orbit();
+
+	reader.app.world.resource_mut::<Input<MouseButton>>().press(MouseButton::Left);
+	for _ in 0..60 {
+		reader.app.world.send_event(MouseMotion { delta: Vec2::new(0.0, 100.0) });
+		reader.step(1.0 / 60.0);
+	}
+
+	let pitch_max = reader.camera().orbit.pitch_max;
+	assert!(pitch(&reader).abs() <= pitch_max + 0.01);
+	assert!(pitch(&reader).abs() > pitch_max - 1.0);
+}
+
+#[test]
+fn orbit_needs_button_held() {
+	let mut reader	= orbit();
+	let before		= reader.translation();
+
+	reader.app.world.send_event(MouseMotion { delta: Vec2::new(100.0, 0.0) });
+	reader.run(60.0, 0.5);
+
+	assert!(reader.translation().distance(before) < 1e-4);
+}
+
+#[test]
+fn distance_is_limited() {
+	let mut reader = orbit();
+
+	for _ in 0..60 {
+		reader.app.world.send_event(MouseWheel { unit: MouseScrollUnit::Line, x: 0.0, y: -50.0 });
+		reader.step(1.0 / 60.0);
+	}
+	reader.run(60.0, 1.0);
+
+	// camera looks straight at the text plane where the pivot is
+	let distance_max = reader.camera().orbit.distance_max;
+	assert!((reader.translation().z - distance_max).abs() < 1e-2);
+}
+
+#[test]
+fn focus_frames_text() {
+	let mut reader	= orbit();
+
+	let transform	= Transform::from_xyz(50.0, 20.0, -30.0);
+	let text		= TextDescriptor { glyph_width: 0.1, glyph_height: 0.2, rows: 50, columns: 100, ..Default::default() };
+	let panel		= reader.app.world.spawn((transform, GlobalTransform::from(transform), text)).id();
+
+	reader.command(CameraCommand::Focus(panel));
+	reader.run(60.0, 2.0);
+
+	// center of 10 x 10 block of text going right and down from its origin
+	let center		= transform.translation + Vec3::new(5.0, -5.0, 0.0);
+	let camera		= *reader.app.world.get::<Transform>(reader.camera).unwrap();
+
+	let to_center	= center - camera.translation;
+	assert!(to_center.normalize().dot(camera.forward()) > 0.9999);
+
+	// whole block fits into default 45 degree field of view
+	let radius		= Vec3::new(5.0, 5.0, 0.0).length();
+	let half_fov	= PerspectiveProjection::default().fov / 2.0;
+	assert!((radius / to_center.length()).asin() < half_fov);
+}
+
+#[test]
+fn camera_spawned_in_orbit_stays_in_place() {
+	let mut reader	= Reader::new();
+	let before		= *reader.app.world.get::<Transform>(reader.camera).unwrap();
+
+	// no set_mode, just like a camera spawned with mode already set
+	reader.camera().mode = CameraMode::Orbit;
+	reader.run(60.0, 1.0);
+
+	let after		= reader.app.world.get::<Transform>(reader.camera).unwrap();
+	assert!(after.translation.distance(before.translation) < 1e-3);
+	assert!(after.rotation.angle_between(before.rotation) < 1e-3);
+}
+
+#[test]
+fn trigger_zoom_follows_zoom_per_second() {
+	let gamepad = Gamepad { id: 0 };
+
+	for zoom_per_second in [20.0, 40.0] {
+		let mut reader = orbit();
+		reader.camera().gamepad.zoom_per_second = zoom_per_second;
+
+		let connection = GamepadConnectionEvent::new(gamepad, GamepadConnection::Connected(GamepadInfo { name: "test".into() }));
+		reader.app.world.send_event(GamepadEvent::from(connection));
+		reader.step(1.0 / 60.0);
+
+		let z = reader.translation().z;
+
+		// right trigger pulled all the way for a tenth of a second
+		reader.app.world.send_event(GamepadEvent::from(GamepadButtonChangedEvent::new(gamepad, GamepadButtonType::RightTrigger2, 1.0)));
+		reader.run(60.0, 0.1);
+		reader.app.world.send_event(GamepadEvent::from(GamepadButtonChangedEvent::new(gamepad, GamepadButtonType::RightTrigger2, 0.0)));
+		reader.run(60.0, 2.0);
+
+		let zoomed = z - reader.translation().z;
+		assert!((zoomed - zoom_per_second * 0.1).abs() < zoom_per_second / 60.0 + 1e-3);
+	}
+}
+
+#[test]
+fn focus_frames_wrapped_rows() {
+	let mut reader	= orbit();
+
+	// 50 rows wrapped into 100 visual rows are twice as tall
+	let transform	= Transform::from_xyz(50.0, 20.0, -30.0);
+	let text		= TextDescriptor { glyph_width: 0.1, glyph_height: 0.2, rows: 50, columns: 100, ..Default::default() };
+	let panel		= reader.app.world.spawn((transform, GlobalTransform::from(transform), text, RowMap::new([2; 50]))).id();
+
+	reader.command(CameraCommand::Focus(panel));
+	reader.run(60.0, 2.0);
+
+	let center		= transform.translation + Vec3::new(5.0, -10.0, 0.0);
+	let camera		= *reader.app.world.get::<Transform>(reader.camera).unwrap();
+
+	let to_center	= center - camera.translation;
+	assert!(to_center.normalize().dot(camera.forward()) > 0.9999);
+
+	let radius		= Vec3::new(5.0, 10.0, 0.0).length();
+	let half_fov	= PerspectiveProjection::default().fov / 2.0;
+	assert!((radius / to_center.length()).asin() < half_fov);
+}
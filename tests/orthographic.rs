@@ -0,0 +1,68 @@
+use bevy :: prelude :: *;
+use bevy :: render :: camera :: ScalingMode;
+
+use bevy_reader_camera :: { * };
+
+mod common;
+use common :: Reader;
+
+fn orthographic() -> Reader {
+	Reader::with_projection(Projection::Orthographic(OrthographicProjection {
+		scaling_mode: ScalingMode::FixedVertical(2.0),
+		..default()
+	}))
+}
+
+fn scale(reader: &Reader) -> f32 {
+	match reader.projection() {
+		Projection::Orthographic(orthographic) => orthographic.scale,
+		Projection::Perspective(_) => panic!("projection isn't orthographic"),
+	}
+}
+
+fn visible(reader: &mut Reader) -> (f32, f32) {
+	let camera = reader.camera();
+	(camera.visible_rows, camera.visible_columns)
+}
+
+#[test]
+fn shows_as_much_text_as_perspective() {
+	let mut perspective	= Reader::new();
+	let mut orthographic = orthographic();
+
+	let (rows, columns) = visible(&mut perspective);
+	let (rows_ortho, columns_ortho) = visible(&mut orthographic);
+
+	assert!((rows - rows_ortho).abs() < 0.01, "{rows} vs {rows_ortho}");
+	assert!((columns - columns_ortho).abs() < 0.01, "{columns} vs {columns_ortho}");
+}
+
+#[test]
+fn zoom_changes_scale() {
+	let mut reader		= orthographic();
+	let scale_before	= scale(&reader);
+	let (rows_before, _) = visible(&mut reader);
+
+	reader.command(CameraCommand::SetZoom(14.0));
+	reader.run(60.0, 1.0);
+
+	let (rows_after, _) = visible(&mut reader);
+
+	assert!((scale(&reader) / scale_before - 2.0).abs() < 0.01);
+	assert!((rows_after / rows_before - 2.0).abs() < 0.01);
+}
+
+#[test]
+fn row_offset_matches_perspective() {
+	let scroll = |mut reader: Reader| {
+		reader.command(CameraCommand::ScrollBy(250));
+		reader.run(60.0, 2.0);
+		(reader.row_offset, reader.translation().y)
+	};
+
+	let (offset, y) = scroll(Reader::new());
+	let (offset_ortho, y_ortho) = scroll(orthographic());
+
+	assert_eq!(offset, offset_ortho);
+	assert!((y - y_ortho).abs() < 0.01);
+}
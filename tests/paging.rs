@@ -0,0 +1,57 @@
+use bevy_reader_camera :: { * };
+
+mod common;
+use common :: Reader;
+
+fn page(reader: &mut Reader) -> u32 {
+	let camera = reader.camera();
+	camera.visible_rows.floor() as u32 - camera.page_overlap_rows
+}
+
+#[test]
+fn pages_keep_overlap_rows_on_screen() {
+	let mut reader = Reader::new();
+
+	reader.command(CameraCommand::ScrollTo(100));
+	reader.run(60.0, 2.0);
+
+	let page_default = page(&mut reader);
+	assert!(page_default > 0);
+
+	reader.command(CameraCommand::PageDown);
+	reader.run(60.0, 2.0);
+	assert_eq!(reader.row_offset, 100 + page_default);
+
+	reader.camera().page_overlap_rows = 5;
+	let page_overlapped = page(&mut reader);
+	assert_eq!(page_overlapped + 3, page_default);
+
+	reader.command(CameraCommand::PageUp);
+	reader.run(60.0, 2.0);
+	assert_eq!(reader.row_offset, 100 + page_default - page_overlapped);
+
+	let half_page = reader.camera().visible_rows.floor() as u32 / 2;
+	reader.command(CameraCommand::HalfPageDown);
+	reader.run(60.0, 2.0);
+	assert_eq!(reader.row_offset, 100 + page_default - page_overlapped + half_page);
+}
+
+#[test]
+fn document_start_and_end() {
+	let mut reader = Reader::new();
+
+	reader.command(CameraCommand::DocumentEnd);
+	reader.run(60.0, 3.0);
+	let row_offset_max = reader.row_offset;
+	assert!(row_offset_max > 9_000);
+	assert_eq!(reader.camera().row_offset_out(), row_offset_max);
+
+	// nothing to page through past the end
+	reader.command(CameraCommand::PageDown);
+	reader.run(60.0, 2.0);
+	assert_eq!(reader.row_offset, row_offset_max);
+
+	reader.command(CameraCommand::DocumentStart);
+	reader.run(60.0, 3.0);
+	assert_eq!(reader.row_offset, 0);
+}
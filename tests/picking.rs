@@ -0,0 +1,73 @@
+use bevy :: prelude :: *;
+
+use bevy_reader_camera :: { * };
+
+mod common;
+use common :: Reader;
+
+#[test]
+fn glyph_and_viewport_positions_round_trip() {
+	let mut reader		= Reader::with_window();
+	let target			= reader.target();
+
+	let world			= &reader.app.world;
+	let camera			= world.get::<Camera>(reader.camera).unwrap();
+	let camera_transform = world.get::<Transform>(reader.camera).unwrap();
+	let target_transform = world.get::<Transform>(target).unwrap();
+	let text			= world.get::<TextDescriptor>(target).unwrap();
+
+	// glyph in the middle of viewport
+	let position		= glyph_to_viewport(110.5, 60.5, camera, camera_transform, target_transform, text, None).unwrap();
+	assert!(position.cmpgt(Vec2::ZERO).all() && position.cmplt(Vec2::new(800.0, 600.0)).all());
+
+	let pick			= viewport_to_glyph(position, camera, camera_transform, target_transform, text, None, None).unwrap();
+	assert_eq!((pick.row, pick.column), (110, 60));
+	assert!((pick.row_fract - 110.5).abs() < 1e-2);
+	assert!((pick.column_fract - 60.5).abs() < 1e-2);
+	assert!(pick.inside_text);
+
+	// rows further down the text are lower on screen
+	let below			= glyph_to_viewport(115.5, 60.5, camera, camera_transform, target_transform, text, None).unwrap();
+	assert!(below.y < position.y);
+}
+
+/// Text of 120 rows with given RowMap, picked at given visual row in the middle column
+fn pick_with_row_map(row_map: RowMap, row: f32) -> (GlyphPick, RowMap) {
+	let mut reader		= Reader::with_window();
+	let target			= reader.target();
+
+	reader.app.world.get_mut::<TextDescriptor>(target).unwrap().rows = 120;
+	reader.app.world.entity_mut(target).insert(row_map.clone());
+	reader.command(CameraCommand::DocumentEnd);
+	reader.run(60.0, 3.0);
+
+	let world			= &reader.app.world;
+	let camera			= world.get::<Camera>(reader.camera).unwrap();
+	let camera_transform = world.get::<Transform>(reader.camera).unwrap();
+	let target_transform = world.get::<Transform>(target).unwrap();
+	let text			= world.get::<TextDescriptor>(target).unwrap();
+
+	let position		= glyph_to_viewport(row, 60.5, camera, camera_transform, target_transform, text, None).unwrap();
+	let pick			= viewport_to_glyph(position, camera, camera_transform, target_transform, text, None, Some(&row_map)).unwrap();
+
+	(pick, row_map)
+}
+
+#[test]
+fn picking_goes_through_wrapped_rows() {
+	// first 100 rows wrap into 2 visual rows each, 220 visual rows in total
+	let (pick, row_map) = pick_with_row_map(RowMap::new([2; 100]), 215.5);
+
+	assert_eq!(pick.row, 215);
+	assert!(pick.inside_text);
+	assert_eq!(row_map.to_logical(pick.row as u32), 115);
+}
+
+#[test]
+fn picking_below_folded_rows_is_outside_of_text() {
+	// first 100 rows are folded, 20 visual rows are left
+	let (pick, _) = pick_with_row_map(RowMap::new([0; 100]), 25.5);
+
+	assert_eq!(pick.row, 19);
+	assert!(!pick.inside_text);
+}
@@ -0,0 +1,96 @@
+use bevy :: prelude :: *;
+
+use bevy_reader_camera :: { * };
+
+mod common;
+use common :: Reader;
+
+// glyphs are slightly in front of text entity
+const GLYPH_Z: f32 = 0.05;
+
+/// Height of text plane the camera shows right now
+fn height_on_text(reader: &Reader) -> f32 {
+	match reader.projection() {
+		Projection::Perspective(perspective) => 2.0 * (reader.translation().z - GLYPH_Z) * (perspective.fov / 2.0).tan(),
+		Projection::Orthographic(orthographic) => orthographic.area.height(),
+	}
+}
+
+fn is_orthographic(reader: &Reader) -> bool {
+	matches!(reader.projection(), Projection::Orthographic(_))
+}
+
+#[test]
+fn text_keeps_its_size_during_transition() {
+	let mut reader	= Reader::new();
+	let height		= height_on_text(&reader);
+	let z			= reader.translation().z;
+
+	reader.command(CameraCommand::SetPerspective(false));
+
+	let mut z_max	= z;
+	for _ in 0..60 {
+		reader.step(1.0 / 60.0);
+
+		z_max		= z_max.max(reader.translation().z);
+		assert!((height_on_text(&reader) - height).abs() < height * 0.001, "{} vs {height}", height_on_text(&reader));
+	}
+
+	// camera backed off during dolly zoom and came back once projection switched
+	assert!(z_max > z * 10.0);
+	assert!(is_orthographic(&reader));
+	assert!((reader.translation().z - z).abs() < 1e-4);
+}
+
+#[test]
+fn visible_rows_stay_the_same() {
+	let mut reader	= Reader::new();
+	let rows		= reader.camera().visible_rows;
+
+	reader.command(CameraCommand::SetPerspective(false));
+	reader.run(60.0, 1.0);
+	assert!((reader.camera().visible_rows - rows).abs() < 0.01);
+
+	reader.command(CameraCommand::SetPerspective(true));
+	reader.run(60.0, 1.0);
+	assert!((reader.camera().visible_rows - rows).abs() < 0.01);
+	assert!(!is_orthographic(&reader));
+}
+
+#[test]
+fn switching_back_halfway_returns_to_perspective() {
+	let mut reader	= Reader::new();
+	let z			= reader.translation().z;
+
+	reader.command(CameraCommand::SetPerspective(false));
+	reader.run(60.0, 0.2);
+	reader.command(CameraCommand::SetPerspective(true));
+
+	// no jump when direction changes
+	let z_before	= reader.translation().z;
+	reader.step(1.0 / 60.0);
+	assert!((reader.translation().z - z_before).abs() < z_before * 0.2);
+
+	reader.run(60.0, 1.0);
+
+	assert!(!is_orthographic(&reader));
+	assert!((reader.translation().z - z).abs() < 1e-4);
+	let Projection::Perspective(perspective) = reader.projection() else { unreachable!() };
+	assert_eq!(perspective.fov, PerspectiveProjection::default().fov);
+}
+
+#[test]
+fn works_in_fly_mode() {
+	let mut reader	= Reader::new();
+
+	reader.command(CameraCommand::SetMode(CameraMode::Fly));
+	reader.run(60.0, 1.0);
+
+	let height		= height_on_text(&reader);
+	reader.command(CameraCommand::SetPerspective(false));
+	reader.run(60.0, 1.0);
+
+	assert!(is_orthographic(&reader));
+	// outside of reader mode the plane of text entity keeps its size, glyphs are a bit closer than that
+	assert!((height_on_text(&reader) - height).abs() < height * 0.01);
+}
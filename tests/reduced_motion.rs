@@ -0,0 +1,104 @@
+use bevy :: prelude :: *;
+use bevy :: input :: mouse :: MouseMotion;
+
+use bevy_reader_camera :: { * };
+
+mod common;
+use common :: Reader;
+
+fn reader(reduced_motion: bool) -> Reader {
+	let mut reader = Reader::new();
+
+	let mut camera = reader.camera();
+	camera.reduced_motion = reduced_motion;
+	camera.enabled_rotation = true;
+
+	reader
+}
+
+/// Largest pitch while dragging text with mouse
+fn lean(reduced_motion: bool) -> f32 {
+	let mut reader = reader(reduced_motion);
+
+	// camera doesn't lean at the start of text
+	reader.command(CameraCommand::ScrollTo(100));
+	reader.run(60.0, 1.0);
+
+	let mut lean = 0.0f32;
+	for _ in 0..30 {
+		reader.app.world.send_event(MouseMotion { delta: Vec2::new(0.0, 10.0) });
+		reader.step(1.0 / 60.0);
+
+		lean = lean.max(reader.pitch().abs());
+	}
+
+	lean
+}
+
+#[test]
+fn no_lean_while_scrolling() {
+	assert!(lean(false) > 0.5);
+	assert_eq!(lean(true), 0.0);
+}
+
+#[test]
+fn short_moves_end_quickly() {
+	let mut reader = reader(true);
+
+	reader.command(CameraCommand::ScrollBy(20));
+	reader.run(60.0, 0.1);
+
+	let settled = reader.translation();
+	reader.run(60.0, 1.0);
+
+	assert!(settled.distance(reader.translation()) < 1e-4);
+}
+
+#[test]
+fn long_jumps_snap_without_flying_over() {
+	let mut reader = reader(true);
+	reader.camera().jump_mode = JumpMode::FlyOver;
+
+	let z = reader.translation().z;
+
+	reader.command(CameraCommand::ScrollTo(5000));
+	for _ in 0..10 {
+		reader.step(1.0 / 60.0);
+		assert!(!reader.camera().is_jumping());
+		assert!(reader.translation().z <= z + 1e-4);
+	}
+}
+
+#[test]
+fn row_offset_reporting_is_unchanged() {
+	let mut normal	= reader(false);
+	let mut reduced	= reader(true);
+
+	for reader in [&mut normal, &mut reduced] {
+		reader.command(CameraCommand::ScrollBy(20));
+		reader.run(60.0, 0.5);
+		reader.command(CameraCommand::PageDown);
+		reader.run(60.0, 0.5);
+		reader.command(CameraCommand::ScrollBy(-7));
+		reader.run(60.0, 1.0);
+	}
+
+	assert_eq!(normal.row_offset, reduced.row_offset);
+	assert_eq!(normal.camera().row_offset_out(), reduced.camera().row_offset_out());
+}
+
+#[test]
+fn flick_stops_right_after_release() {
+	let mut reader = Reader::with_window();
+	reader.camera().reduced_motion = true;
+
+	reader.gesture(&[Vec2::new(400.0, 450.0)], &[Vec2::new(400.0, 250.0)], 4);
+	reader.step(1.0 / 60.0);
+
+	let released = reader.row_offset;
+	assert!(released > 100);
+
+	// snapping to the closest row is all that happens after release
+	reader.run(60.0, 3.0);
+	assert!(reader.row_offset <= released + 1);
+}
@@ -0,0 +1,122 @@
+use bevy :: input :: mouse :: { MouseScrollUnit, MouseWheel };
+
+use bevy_reader_camera :: { * };
+
+mod common;
+use common :: { GLYPH_HEIGHT, Reader };
+
+fn descriptor() -> TextDescriptor {
+	TextDescriptor { glyph_width: 0.1, glyph_height: GLYPH_HEIGHT, rows: 10_000, columns: 120, ..Default::default() }
+}
+
+// every tenth row is a tall heading, then a code block of short rows
+fn heights() -> RowHeights {
+	RowHeights::new((0..1000).map(|row| match row % 10 {
+		0		=> 1.0,
+		5..=7	=> 0.1,
+		_		=> GLYPH_HEIGHT,
+	}))
+}
+
+fn reader() -> Reader {
+	let mut reader	= Reader::new();
+	let target		= reader.target();
+	reader.app.world.entity_mut(target).insert(heights());
+	reader.run(60.0, 1.0);
+
+	reader
+}
+
+/// Distance from the start of text to the top edge of viewport
+fn top_edge(reader: &mut Reader) -> f32 {
+	let half_height = reader.camera().visible_height / 2.0;
+	-reader.translation().y - half_height
+}
+
+#[test]
+fn layout_is_prefix_sum_of_heights() {
+	let descriptor	= descriptor();
+	let mut heights	= RowHeights::new([1.0, 0.5, 2.0]);
+	let layout		= RowLayout::new(&descriptor, Some(&heights));
+
+	assert_eq!(layout.top(2.0), 1.5);
+	assert_eq!(layout.top(2.5), 2.5);
+	assert_eq!(layout.row_at(2.5), 2.5);
+	// rows past the table are glyph_height tall
+	assert!((layout.top(5.0) - (3.5 + 2.0 * GLYPH_HEIGHT)).abs() < 1e-5);
+	assert!((layout.row_at(3.5 + GLYPH_HEIGHT) - 4.0).abs() < 1e-5);
+
+	heights.set(0, 0.0);
+	let layout		= RowLayout::new(&descriptor, Some(&heights));
+	assert_eq!(layout.top(2.0), 0.5);
+	// folded row is skipped over
+	assert_eq!(layout.row_at(0.0), 1.0);
+}
+
+#[test]
+fn uniform_layout_without_heights() {
+	let descriptor	= descriptor();
+	let layout		= RowLayout::new(&descriptor, None);
+
+	assert!((layout.top(12.5) - 12.5 * GLYPH_HEIGHT).abs() < 1e-5);
+	assert!((layout.row_at(12.5 * GLYPH_HEIGHT) - 12.5).abs() < 1e-5);
+	assert_eq!(layout.height(3), GLYPH_HEIGHT);
+}
+
+#[test]
+fn scrolled_row_is_at_the_top_edge() {
+	let mut reader	= reader();
+	let descriptor	= descriptor();
+	let heights		= heights();
+	let layout		= RowLayout::new(&descriptor, Some(&heights));
+
+	for row in [30, 45, 117] {
+		reader.command(CameraCommand::ScrollTo(row));
+		reader.run(60.0, 2.0);
+
+		assert_eq!(reader.row_offset, row);
+		assert!((top_edge(&mut reader) - layout.top(row as f32)).abs() < 1e-3, "row {row}");
+	}
+}
+
+#[test]
+fn visible_rows_follow_row_heights() {
+	let mut reader	= reader();
+	let descriptor	= descriptor();
+	let heights		= heights();
+	let layout		= RowLayout::new(&descriptor, Some(&heights));
+
+	reader.command(CameraCommand::ScrollTo(100));
+	reader.run(60.0, 2.0);
+
+	let visible_height = reader.camera().visible_height;
+	let visible_rows = reader.camera().visible_rows;
+	let expected	= layout.rows_in(layout.top(100.0), visible_height);
+
+	assert!((visible_rows - expected).abs() < 0.01, "{visible_rows} vs {expected}");
+	// tall headings take more space than uniform rows would
+	assert!(visible_rows < visible_height / GLYPH_HEIGHT);
+}
+
+#[test]
+fn wheel_scrolling_snaps_to_row_boundaries() {
+	let mut reader	= reader();
+	let descriptor	= descriptor();
+	let heights		= heights();
+	let layout		= RowLayout::new(&descriptor, Some(&heights));
+
+	reader.command(CameraCommand::ScrollTo(100));
+	reader.run(60.0, 2.0);
+
+	for _ in 0..10 {
+		reader.app.world.send_event(MouseWheel { unit: MouseScrollUnit::Line, x: 0.0, y: -1.0 });
+		reader.step(1.0 / 60.0);
+	}
+	reader.run(60.0, 3.0);
+
+	// scrolled through the heading and came to rest exactly at the top of some row
+	let row			= layout.row_at(top_edge(&mut reader));
+	assert!(reader.row_offset > 100);
+	assert!((row - row.round()).abs() < 1e-3, "stopped in between rows at {row}");
+	assert_eq!(row.round() as u32, reader.row_offset);
+}
@@ -0,0 +1,93 @@
+use bevy_reader_camera :: { * };
+
+mod common;
+use common :: Reader;
+
+// rows 10..20 are folded, row 30 is soft-wrapped into 4 visual rows
+fn row_map() -> RowMap {
+	let mut row_map = RowMap::new((0..200).map(|row| if row == 30 { 4 } else { 1 }));
+	row_map.hide(10..20);
+	row_map
+}
+
+fn reader() -> Reader {
+	let mut reader	= Reader::new();
+	let target		= reader.target();
+	reader.app.world.entity_mut(target).insert(row_map());
+	reader.run(60.0, 1.0);
+
+	reader
+}
+
+#[test]
+fn logical_and_visual_rows_map_both_ways() {
+	let row_map = row_map();
+
+	assert_eq!(row_map.to_visual(9), 9);
+	// folded rows take no space
+	assert!(row_map.is_hidden(15));
+	assert_eq!(row_map.to_visual(15), 10);
+	assert_eq!(row_map.to_visual(20), 10);
+	assert_eq!(row_map.to_logical(10), 20);
+	// every visual row of wrapped row belongs to it
+	assert_eq!(row_map.to_visual(30), 20);
+	assert_eq!(row_map.to_visual(31), 24);
+	for visual in 20..24 {
+		assert_eq!(row_map.to_logical(visual), 30);
+	}
+	// rows past the table are one to one
+	assert_eq!(row_map.to_visual(250), 243);
+	assert_eq!(row_map.to_logical(243), 250);
+}
+
+#[test]
+fn app_speaks_logical_rows() {
+	let mut reader = reader();
+
+	reader.command(CameraCommand::ScrollTo(40));
+	reader.run(60.0, 2.0);
+
+	assert_eq!(reader.row_offset, 40);
+	assert_eq!(reader.camera().row_offset_out(), 40);
+	assert_eq!(reader.camera().row_offset_in(), 40);
+	assert!((reader.top_row() - 33.0).abs() < 1e-3);
+}
+
+#[test]
+fn camera_scrolls_through_wrapped_row() {
+	let mut reader = reader();
+
+	reader.command(CameraCommand::ScrollTo(30));
+	reader.run(60.0, 2.0);
+	reader.command(CameraCommand::ScrollBy(2));
+	reader.run(60.0, 2.0);
+
+	// still the same logical row for the app, camera moved by two visual rows
+	assert_eq!(reader.row_offset, 30);
+	assert_eq!(reader.camera().row_offset_out(), 30);
+	assert!((reader.top_row() - 22.0).abs() < 1e-3);
+
+	reader.command(CameraCommand::ScrollBy(2));
+	reader.run(60.0, 2.0);
+
+	assert_eq!(reader.row_offset, 31);
+	assert!((reader.top_row() - 24.0).abs() < 1e-3);
+}
+
+#[test]
+fn folding_above_keeps_logical_row() {
+	let mut reader	= Reader::new();
+
+	reader.command(CameraCommand::ScrollTo(100));
+	reader.run(60.0, 2.0);
+	assert!((reader.top_row() - 100.0).abs() < 1e-3);
+
+	let target		= reader.target();
+	reader.app.world.entity_mut(target).insert(row_map());
+	reader.run(60.0, 2.0);
+
+	// app wasn't asked to scroll, camera moved up by the folded rows
+	assert_eq!(reader.row_offset, 100);
+	assert_eq!(reader.camera().row_offset_out(), 100);
+	assert!((reader.top_row() - 93.0).abs() < 1e-3);
+}
@@ -0,0 +1,72 @@
+use bevy_reader_camera :: { * };
+
+mod common;
+use common :: Reader;
+
+#[test]
+fn requests_wait_for_delayed_ack() {
+	let mut reader	= Reader::at(100);
+	reader.auto_ack	= false;
+
+	reader.command(CameraCommand::ScrollBy(10));
+	reader.run(60.0, 1.0);
+
+	// asked once and not again while app is busy
+	assert_eq!(reader.requested, 10);
+	assert_eq!(reader.camera().row_offset_in(), 100);
+	assert_eq!(reader.camera().row_offset_delta(), 10);
+
+	reader.ack(110);
+	reader.run(60.0, 1.0);
+
+	assert_eq!(reader.requested, 0);
+	assert_eq!(reader.camera().row_offset_in(), 110);
+	assert_eq!(reader.camera().row_offset_delta(), 0);
+	assert_eq!(reader.camera().row_offset_out(), 110);
+}
+
+#[test]
+fn clamped_ack_wins() {
+	let mut reader	= Reader::at(100);
+	reader.auto_ack	= false;
+
+	reader.command(CameraCommand::ScrollBy(10));
+	reader.step(1.0 / 60.0);
+	assert_eq!(reader.requested, 10);
+
+	// app could only scroll by 4 rows, camera settles there instead of asking again
+	reader.ack(104);
+	reader.run(60.0, 2.0);
+
+	assert_eq!(reader.requested, 0);
+	assert_eq!(reader.camera().row_offset_delta(), 0);
+	assert_eq!(reader.camera().row_offset_out(), 104);
+}
+
+#[test]
+fn zoom_keeps_unacknowledged_scroll() {
+	// how far zooming alone moves the top edge
+	let mut reference = Reader::at(100);
+	reference.command(CameraCommand::SetZoom(12.0));
+	reference.run(60.0, 2.0);
+	let zoom_rows = reference.row_offset as i32 - 100;
+	assert!(zoom_rows != 0);
+
+	let mut reader	= Reader::at(100);
+	reader.auto_ack	= false;
+
+	reader.command(CameraCommand::ScrollBy(1));
+	reader.step(1.0 / 60.0);
+	assert_eq!(reader.requested, 1);
+
+	reader.command(CameraCommand::SetZoom(12.0));
+	reader.run(60.0, 2.0);
+
+	assert_eq!(reader.requested, 1 + zoom_rows);
+
+	reader.ack((100 + 1 + zoom_rows) as u32);
+	reader.run(60.0, 2.0);
+
+	assert_eq!(reader.requested, 0);
+	assert_eq!(reader.camera().row_offset_out() as i32, 100 + 1 + zoom_rows);
+}
@@ -0,0 +1,67 @@
+use bevy_reader_camera :: { * };
+
+mod common;
+use common :: Reader;
+
+#[test]
+fn rows_inserted_above_keep_content_in_place() {
+	let mut reader = Reader::at(100);
+
+	reader.edit_rows(20, 0, 5);
+	reader.step(1.0 / 60.0);
+
+	// moved in the same frame without easing
+	assert!((reader.top_row() - 105.0).abs() < 1e-3);
+	assert_eq!(reader.camera().row_offset_out(), 105);
+	assert_eq!(reader.camera().row_offset_in(), 105);
+
+	reader.run(60.0, 2.0);
+
+	assert!((reader.top_row() - 105.0).abs() < 1e-3);
+	assert_eq!(reader.row_offset, 105);
+}
+
+#[test]
+fn rows_removed_above_keep_content_in_place() {
+	let mut reader = Reader::at(100);
+
+	reader.edit_rows(50, 10, 0);
+	reader.step(1.0 / 60.0);
+
+	assert!((reader.top_row() - 90.0).abs() < 1e-3);
+	assert_eq!(reader.camera().row_offset_out(), 90);
+
+	// removing the row at the top edge puts the row that took its place there
+	reader.edit_rows(85, 10, 2);
+	reader.step(1.0 / 60.0);
+
+	assert!((reader.top_row() - 85.0).abs() < 1e-3);
+	assert_eq!(reader.camera().row_offset_out(), 85);
+}
+
+#[test]
+fn rows_edited_below_leave_camera_alone() {
+	let mut reader = Reader::at(100);
+	let translation = reader.translation();
+
+	reader.edit_rows(500, 3, 40);
+	reader.run(60.0, 1.0);
+
+	assert_eq!(reader.translation(), translation);
+	assert_eq!(reader.camera().row_offset_out(), 100);
+}
+
+#[test]
+fn anchoring_keeps_scrolling_going() {
+	let mut reader = Reader::at(100);
+
+	reader.command(CameraCommand::ScrollBy(10));
+	reader.step(1.0 / 60.0);
+	// app answers the request and edits text in the same frame
+	reader.edit_rows(0, 0, 20);
+	reader.run(60.0, 2.0);
+
+	assert!((reader.top_row() - 130.0).abs() < 1e-3);
+	assert_eq!(reader.row_offset, 130);
+	assert_eq!(reader.camera().row_offset_out(), 130);
+}
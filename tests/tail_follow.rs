@@ -0,0 +1,83 @@
+use bevy_reader_camera :: { * };
+
+mod common;
+use common :: Reader;
+
+fn rows(reader: &mut Reader) -> u32 {
+	let target = reader.target();
+	reader.app.world.get::<TextDescriptor>(target).unwrap().rows as u32
+}
+
+// app appends rows to the end of text like a log does
+fn append(reader: &mut Reader, rows_new: u32) {
+	let rows = rows(reader);
+	reader.edit_rows(rows, 0, rows_new);
+}
+
+fn reader_at_end(tail_follow: bool) -> Reader {
+	let mut reader = Reader::new();
+	reader.camera().tail_follow = tail_follow;
+	reader.command(CameraCommand::DocumentEnd);
+	reader.run(60.0, 2.0);
+
+	reader
+}
+
+#[test]
+fn follows_new_rows_smoothly() {
+	let mut reader	= reader_at_end(true);
+	let row_offset	= reader.row_offset;
+	assert!(reader.camera().is_tail_following());
+
+	append(&mut reader, 5);
+	reader.step(1.0 / 60.0);
+	reader.step(1.0 / 60.0);
+
+	// eases towards new rows instead of teleporting there
+	let top = reader.top_row();
+	assert!(top > row_offset as f32 && top < (row_offset + 5) as f32);
+
+	reader.run(60.0, 2.0);
+
+	assert_eq!(reader.row_offset, row_offset + 5);
+	assert!((reader.top_row() - (row_offset + 5) as f32).abs() < 1e-3);
+	assert!(reader.camera().is_tail_following());
+}
+
+#[test]
+fn scrolling_up_pauses_following() {
+	let mut reader	= reader_at_end(true);
+
+	reader.command(CameraCommand::ScrollBy(-5));
+	reader.run(60.0, 2.0);
+	let row_offset	= reader.row_offset;
+	assert!(!reader.camera().is_tail_following());
+
+	append(&mut reader, 20);
+	reader.run(60.0, 2.0);
+
+	assert_eq!(reader.row_offset, row_offset);
+
+	// back at the end follows again
+	reader.command(CameraCommand::DocumentEnd);
+	reader.run(60.0, 2.0);
+	let row_offset	= reader.row_offset;
+	assert!(reader.camera().is_tail_following());
+
+	append(&mut reader, 20);
+	reader.run(60.0, 2.0);
+
+	assert_eq!(reader.row_offset, row_offset + 20);
+}
+
+#[test]
+fn disabled_by_default() {
+	let mut reader	= reader_at_end(false);
+	let row_offset	= reader.row_offset;
+
+	append(&mut reader, 20);
+	reader.run(60.0, 2.0);
+
+	assert_eq!(reader.row_offset, row_offset);
+	assert!(!reader.camera().is_tail_following());
+}
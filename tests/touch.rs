@@ -0,0 +1,48 @@
+use bevy :: prelude :: *;
+
+mod common;
+use common :: Reader;
+
+#[test]
+fn text_follows_dragging_finger() {
+	let mut reader	= Reader::with_window();
+	reader.camera().momentum.enabled = false;
+
+	// half of 600 pixels high window
+	let rows		= reader.camera().visible_rows / 2.0;
+
+	reader.gesture(&[Vec2::new(400.0, 450.0)], &[Vec2::new(400.0, 150.0)], 10);
+	reader.run(60.0, 2.0);
+
+	assert!((reader.row_offset as f32 - (100.0 + rows)).abs() <= 1.0);
+
+	// and back down the screen towards the start of text
+	reader.gesture(&[Vec2::new(400.0, 150.0)], &[Vec2::new(400.0, 450.0)], 10);
+	reader.run(60.0, 2.0);
+
+	assert!((reader.row_offset as i32 - 100).abs() <= 1);
+}
+
+#[test]
+fn pinch_zooms_in_when_fingers_move_apart() {
+	let mut reader	= Reader::with_window();
+	reader.camera().enabled_zoom = true;
+
+	let z			= reader.translation().z;
+	let zoom		= reader.camera().target_zoom;
+
+	reader.gesture(&[Vec2::new(350.0, 300.0), Vec2::new(450.0, 300.0)], &[Vec2::new(300.0, 300.0), Vec2::new(500.0, 300.0)], 10);
+	reader.run(60.0, 2.0);
+
+	assert!(reader.camera().target_zoom < zoom * 0.75);
+	assert!(reader.translation().z < z);
+
+	// zoom is disabled by default
+	let mut reader	= Reader::with_window();
+	let zoom		= reader.camera().target_zoom;
+
+	reader.gesture(&[Vec2::new(350.0, 300.0), Vec2::new(450.0, 300.0)], &[Vec2::new(300.0, 300.0), Vec2::new(500.0, 300.0)], 10);
+	reader.run(60.0, 2.0);
+
+	assert_eq!(reader.camera().target_zoom, zoom);
+}